@@ -0,0 +1,34 @@
+//! Plays a game of random players on the classic board that can be followed and paused over HTTP.
+//! The game starts paused, `curl -X POST <address>/resume` starts it.
+//!
+//! Usage: `cargo run --example api -- [address]`, the address must be a loopback address
+use std::env;
+use std::rc::Rc;
+
+use colored::Color::{Black, Blue, Green, Red, White};
+use rist::api::Api;
+use rist::boards::BoardStruct;
+use rist::boards::BoardType::ClassicBoard;
+use rist::players::{Player, RandomPlayer};
+use rist::Game;
+
+fn main() -> std::io::Result<()> {
+    let address = env::args().nth(1).unwrap_or(String::from("127.0.0.1:8080"));
+    let api = Api::new(address)?;
+    api.pause();
+    println!("Serving the game on http://{}", api.address());
+
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(RandomPlayer::new("Player 1", Red, White)),
+        Rc::new(RandomPlayer::new("Player 2", Green, White)),
+        Rc::new(RandomPlayer::new("Player 3", Blue, White)),
+        Rc::new(RandomPlayer::new("Player 4", White, Black)),
+    ];
+    let board = BoardStruct::new(ClassicBoard, players.len(), None);
+    let mut game = Game::new(players, board);
+    game.setup(false);
+    let result = api.play(&mut game, Some(1000));
+
+    println!("The game has ended by {}", result.victory);
+    Ok(())
+}
@@ -0,0 +1,115 @@
+//! A thin terminal client to play or follow a game hosted by a server.
+//! Decisions are typed as the JSON replies of the bot protocol.
+//!
+//! Usage: `cargo run --example client -- <address> (<name> | --rejoin <token> | --spectate)`
+use std::env;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+use rist::server::Hello;
+use serde_json::Value;
+
+/// How to reply to every decision
+fn hint(kind: &str) -> &str {
+    match kind {
+        "claim" => "index in the free territories, e.g. 0",
+        "place" => "territories and armies, e.g. [[12, 3]]",
+        "commanders" => "commanders and territories, e.g. [[\"Land\", 12]]",
+        "attack" => "null, or {\"dice\": 3, \"attacker\": 12, \"defender\": 13}",
+        "capture" => "armies to move in, e.g. 3",
+        "defend" => "amount of dice, e.g. 2",
+        "defend_blitz" => "\"MaxDice\", {\"Dice\": 1} or \"PerRoll\"",
+        "fortify" => "null, or the territories and armies, e.g. [12, 14, 3]",
+        "surrender" => "true or false",
+        _ => "",
+    }
+}
+
+/// Prints the territories of the player and what it is asked
+fn show(message: &Value) {
+    let game = &message["game"];
+    let player = &game["board"]["player"];
+    println!(
+        "\nTurn {}, {} armies in reserve",
+        game["turn"],
+        game["players"][player.as_u64().unwrap() as usize]["reserve"]
+    );
+    for territory in game["board"]["territories"].as_array().unwrap() {
+        if territory["owner"] == *player {
+            println!(
+                "  {:>2} {:<24} {:>3} armies, next to {}",
+                territory["index"],
+                territory["name"].as_str().unwrap(),
+                territory["armies"],
+                territory["connections"]
+            );
+        }
+    }
+    if message["type"] == "claim" {
+        let territories = game["board"]["territories"].as_array().unwrap();
+        for (index, territory) in game["board"]["free_territories"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .enumerate()
+        {
+            let territory = &territories[territory.as_u64().unwrap() as usize];
+            println!(
+                "  {index:>2} {} is free",
+                territory["name"].as_str().unwrap()
+            );
+        }
+    }
+    if !message["attack"].is_null() {
+        println!("Attack: {}", message["attack"]);
+    }
+}
+
+fn main() -> io::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let hello = match args.get(1).map(String::as_str) {
+        Some("--spectate") => Hello::Spectate,
+        Some("--rejoin") => Hello::Rejoin(args[2].clone()),
+        Some(name) => Hello::Join(String::from(name)),
+        None => {
+            eprintln!("Usage: client <address> (<name> | --rejoin <token> | --spectate)");
+            return Ok(());
+        }
+    };
+
+    let mut stream = TcpStream::connect(&args[0])?;
+    stream.set_nodelay(true)?;
+    writeln!(stream, "{}", serde_json::to_string(&hello).unwrap())?;
+
+    let stdin = io::stdin();
+    for line in BufReader::new(stream.try_clone()?).lines() {
+        let message: Value = serde_json::from_str(&line?).unwrap();
+        let kind = message["type"].as_str().unwrap_or_default();
+        match kind {
+            "seat" => println!(
+                "Seated at {}, rejoin with token {}",
+                message["seat"],
+                message["token"].as_str().unwrap()
+            ),
+            "error" => {
+                println!("{}", message["message"]);
+                break;
+            }
+            "event" => println!("{}", message["event"]),
+            "game_start" => println!("The game starts"),
+            "game_over" => {
+                println!("The game is over: {}", message["result"]);
+                break;
+            }
+            _ => {
+                show(&message);
+                print!("{kind} ({}) > ", hint(kind));
+                io::stdout().flush()?;
+                let mut reply = String::new();
+                stdin.lock().read_line(&mut reply)?;
+                write!(stream, "{reply}")?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,45 @@
+//! Hosts a game on the classic board for players that connect over TCP.
+//!
+//! Usage: `cargo run --example server -- [address] [seats]`
+use std::env;
+use std::rc::Rc;
+use std::time::Duration;
+
+use rist::boards::BoardStruct;
+use rist::boards::BoardType::ClassicBoard;
+use rist::players::Player;
+use rist::server::Server;
+use rist::Game;
+
+fn main() -> std::io::Result<()> {
+    let mut args = env::args().skip(1);
+    let address = args.next().unwrap_or(String::from("127.0.0.1:7878"));
+    let seats = args.next().map_or(2, |seats| seats.parse().unwrap());
+
+    let server = Server::new(address, seats)?;
+    println!("Waiting for {seats} players on {}", server.address());
+
+    let players: Vec<Rc<dyn Player>> = server
+        .players(Duration::from_secs(300))
+        .into_iter()
+        .map(|player| Rc::new(player) as Rc<dyn Player>)
+        .collect();
+    for player in &players {
+        println!("{} has joined", player.get_state().name);
+    }
+
+    let board = BoardStruct::new(ClassicBoard, players.len(), None);
+    let mut game = Game::new(players, board);
+    game.setup(false);
+    let result = server.play(&mut game, Some(1000));
+
+    println!("The game has ended by {}:", result.victory);
+    for (rank, player) in result.ranking.iter().enumerate() {
+        println!(
+            "{}. {}",
+            rank + 1,
+            game.get_players()[*player].get_state().name
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+//! Plays a game on the classic board in a full-screen terminal interface,
+//! as a human against random players or, with `--watch`, only between random players.
+//!
+//! Usage: `cargo run --example tui -- [--watch]`
+use std::env;
+use std::rc::Rc;
+
+use colored::Color::{Black, Blue, Green, Red, White};
+use rist::boards::BoardStruct;
+use rist::boards::BoardType::ClassicBoard;
+use rist::players::{Player, RandomPlayer};
+use rist::tui::Tui;
+use rist::Game;
+
+fn main() -> std::io::Result<()> {
+    let tui = Tui::new()?;
+    let first: Rc<dyn Player> = if env::args().any(|arg| arg == "--watch") {
+        Rc::new(RandomPlayer::new("Player 1", Red, White))
+    } else {
+        Rc::new(tui.human("You", Red, White))
+    };
+    let players: Vec<Rc<dyn Player>> = vec![
+        first,
+        Rc::new(RandomPlayer::new("Player 2", Green, White)),
+        Rc::new(RandomPlayer::new("Player 3", Blue, White)),
+        Rc::new(RandomPlayer::new("Player 4", White, Black)),
+    ];
+
+    let board = BoardStruct::new(ClassicBoard, players.len(), None);
+    let mut game = Game::new(players, board);
+    tui.setup(&mut game);
+    tui.play(&mut game, Some(1000));
+    tui.wait();
+    Ok(())
+}
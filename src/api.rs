@@ -0,0 +1,317 @@
+//! An embedded HTTP server that exposes a running game on localhost, e.g. for a browser dashboard.
+//!
+//! | request         | response                                                            |
+//! |-----------------|---------------------------------------------------------------------|
+//! | `GET /status`   | the turn, round, phase, current player, whether the game is paused  |
+//! | `GET /board`    | the continents and territories, with their owners and armies        |
+//! | `GET /players`  | the territories, armies, reserve and continents of every player     |
+//! | `GET /history`  | all events so far, `?since=<n>` skips the first `n` events          |
+//! | `GET /events`   | a WebSocket that streams every event, starting with the history     |
+//! | `POST /pause`   | pauses the game after the current phase                             |
+//! | `POST /step`    | plays a single phase of a paused game                               |
+//! | `POST /resume`  | continues a paused game                                             |
+//!
+//! All responses are JSON, in the same format as the messages of an `ExternalPlayer`.
+//! The API has no authentication, so it only serves on loopback addresses.
+//! Only the read-only `GET` requests may be made from any origin, e.g. by a dashboard page.
+//! The game itself only runs on the thread that calls `Api::play`,
+//! which publishes a snapshot of the game after every phase.
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tungstenite::handshake::derive_accept_key;
+use tungstenite::protocol::Role;
+use tungstenite::{Message, WebSocket};
+
+use crate::rules::FogOfWar;
+use crate::victory::GameResult;
+use crate::view::{BoardView, ContinentView, TerritoryView};
+use crate::Game;
+
+/// The statistics of a single player
+#[derive(Debug, Clone, Serialize)]
+struct PlayerStats {
+    index: usize,
+    name: String,
+    territories: usize,
+    /// The armies on the board
+    armies: u32,
+    reserve: u32,
+    continents: Vec<usize>,
+    defeated: bool,
+}
+
+/// The board as everyone sees it without fog of war
+#[derive(Debug, Clone, Serialize)]
+struct BoardState {
+    continents: Vec<ContinentView>,
+    territories: Vec<TerritoryView>,
+}
+
+/// What the game thread shares with the requests
+#[derive(Default)]
+struct Shared {
+    status: Value,
+    board: Value,
+    players: Value,
+    history: Vec<Value>,
+    paused: bool,
+    /// The phases a paused game may still play
+    steps: u32,
+    /// The WebSockets that follow the events
+    sockets: Vec<Sender<String>>,
+    /// Whether `Api::play` has returned, which closes the WebSockets
+    over: bool,
+}
+
+pub struct Api {
+    address: SocketAddr,
+    shared: Arc<(Mutex<Shared>, Condvar)>,
+}
+
+impl Api {
+    /// Starts serving requests on the given address, which must be a loopback address
+    pub fn new(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        if !listener.local_addr()?.ip().is_loopback() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "The API has no authentication and only serves on loopback addresses",
+            ));
+        }
+        let api = Api {
+            address: listener.local_addr()?,
+            shared: Arc::new((Mutex::new(Shared::default()), Condvar::new())),
+        };
+
+        let shared = Arc::clone(&api.shared);
+        thread::spawn(move || {
+            for stream in listener.incoming().map_while(Result::ok) {
+                let shared = Arc::clone(&shared);
+                thread::spawn(move || {
+                    let _ = handle(stream, &shared);
+                });
+            }
+        });
+        Ok(api)
+    }
+
+    /// The address the requests are served on
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Pauses the game before its next phase, like `POST /pause`
+    pub fn pause(&self) {
+        self.shared.0.lock().unwrap().paused = true;
+    }
+
+    /// Plays the game that has been set up, pausing whenever requested.
+    /// The game is stopped at the given turn limit and ranked by the tiebreak.
+    pub fn play(&self, game: &mut Game, max_turns: Option<u64>) -> GameResult {
+        self.publish(game);
+        loop {
+            self.wait();
+            let more = game.step(false) && max_turns.is_none_or(|max| game.get_turn() < max);
+            self.publish(game);
+            if !more {
+                break;
+            }
+        }
+        let result = game.tiebreak();
+        self.publish(game);
+        let mut shared = self.shared.0.lock().unwrap();
+        shared.over = true;
+        shared.sockets.clear();
+        result
+    }
+
+    /// Waits while the game is paused and no step is requested
+    fn wait(&self) {
+        let (shared, changed) = &*self.shared;
+        let mut shared = changed
+            .wait_while(shared.lock().unwrap(), |shared| {
+                shared.paused && shared.steps == 0
+            })
+            .unwrap();
+        shared.steps = shared.steps.saturating_sub(1);
+    }
+
+    /// Takes a snapshot of the game and streams the new events
+    fn publish(&self, game: &Game) {
+        let board = BoardView::new(game.get_board(), 0, FogOfWar::Disabled);
+        let board = BoardState {
+            continents: board.continents,
+            territories: board.territories,
+        };
+        let players: Vec<PlayerStats> = game
+            .get_players()
+            .iter()
+            .zip(game.get_seats())
+            .map(|(player, seat)| {
+                let state = player.get_state();
+                let territories = seat.get_territories().borrow();
+                PlayerStats {
+                    index: state.index(),
+                    name: String::from(&state.name),
+                    territories: territories.len(),
+                    armies: territories
+                        .iter()
+                        .map(|territory| *territory.armies.borrow())
+                        .sum(),
+                    reserve: *seat.armies.borrow(),
+                    continents: seat
+                        .get_continents()
+                        .borrow()
+                        .iter()
+                        .map(|continent| *continent.index.borrow())
+                        .collect(),
+                    defeated: *seat.defeated.borrow(),
+                }
+            })
+            .collect();
+
+        let mut shared = self.shared.0.lock().unwrap();
+        shared.status = json!({
+            "turn": game.get_turn(),
+            "round": game.get_round(),
+            "phase": game.get_phase().to_string(),
+            "current_player": game.get_current_player(),
+            "paused": shared.paused,
+            "result": game.get_result(),
+        });
+        shared.board = json!(board);
+        shared.players = json!(players);
+
+        let history = game.get_history().borrow();
+        let sent = shared.history.len();
+        for event in &history[sent..] {
+            let event = json!(event);
+            let line = event.to_string();
+            shared
+                .sockets
+                .retain(|socket| socket.send(line.clone()).is_ok());
+            shared.history.push(event);
+        }
+    }
+}
+
+/// Answers a single request
+fn handle(stream: TcpStream, shared: &(Mutex<Shared>, Condvar)) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    let mut key = None;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Sec-WebSocket-Key") {
+                key = Some(String::from(value.trim()));
+            }
+        }
+    }
+
+    let mut words = request.split_whitespace();
+    let method = words.next().unwrap_or_default();
+    let target = words.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let (status, body) = match (method, path) {
+        ("GET", "/events") => match key {
+            Some(key) => return stream_events(stream, &key, shared),
+            None => ("400 Bad Request", json!({"error": "Expected a WebSocket"})),
+        },
+        ("GET", "/status") => ("200 OK", shared.0.lock().unwrap().status.clone()),
+        ("GET", "/board") => ("200 OK", shared.0.lock().unwrap().board.clone()),
+        ("GET", "/players") => ("200 OK", shared.0.lock().unwrap().players.clone()),
+        ("GET", "/history") => {
+            let since = query
+                .split('&')
+                .find_map(|parameter| parameter.strip_prefix("since="))
+                .and_then(|since| since.parse().ok())
+                .unwrap_or(0);
+            let shared = shared.0.lock().unwrap();
+            let since = shared.history.len().min(since);
+            ("200 OK", json!(shared.history[since..]))
+        }
+        ("POST", "/pause" | "/step" | "/resume") => {
+            let mut state = shared.0.lock().unwrap();
+            match path {
+                "/pause" => state.paused = true,
+                "/step" => state.steps += 1,
+                _ => state.paused = false,
+            }
+            state.status["paused"] = json!(state.paused);
+            shared.1.notify_all();
+            ("200 OK", state.status.clone())
+        }
+        (_, "/status" | "/board" | "/players" | "/history" | "/events") => {
+            ("405 Method Not Allowed", json!({"error": "Expected GET"}))
+        }
+        (_, "/pause" | "/step" | "/resume") => {
+            ("405 Method Not Allowed", json!({"error": "Expected POST"}))
+        }
+        _ => ("404 Not Found", json!({"error": "Unknown endpoint"})),
+    };
+
+    // Other origins may read the game, but not control it
+    let cors = match method {
+        "GET" => "Access-Control-Allow-Origin: *\r\n",
+        _ => "",
+    };
+    let body = body.to_string();
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         {cors}\
+         Connection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// Upgrades the request to a WebSocket that receives every event as a text message
+fn stream_events(
+    mut stream: TcpStream,
+    key: &str,
+    shared: &(Mutex<Shared>, Condvar),
+) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\n\
+         Upgrade: websocket\r\n\
+         Connection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        derive_accept_key(key.as_bytes())
+    )?;
+    let mut socket = WebSocket::from_raw_socket(stream, Role::Server, None);
+
+    // The history is sent first, the same lock makes sure no event is missed
+    let (sender, events) = mpsc::channel();
+    let history: Vec<String> = {
+        let mut shared = shared.0.lock().unwrap();
+        if !shared.over {
+            shared.sockets.push(sender);
+        }
+        shared.history.iter().map(Value::to_string).collect()
+    };
+    for event in history.into_iter().chain(events) {
+        if socket.send(Message::Text(event)).is_err() {
+            break;
+        }
+    }
+    let _ = socket.close(None);
+    Ok(())
+}
@@ -9,12 +9,16 @@ use std::rc::Rc;
 use std::time::Duration;
 use std::{io, thread};
 
+use crate::boards::classic_board::ClassicRenderer;
+use crate::boards::renderer::{ListRenderer, Renderer};
 use crate::continent::Continent;
-use crate::players::Player;
+use crate::players::{Player, Seat};
 use crate::territory::Territory;
 use crate::{continent, territory};
 
 pub mod classic_board;
+pub mod renderer;
+pub mod svg;
 
 #[derive(Debug)]
 pub enum BoardType {
@@ -31,12 +35,15 @@ pub struct BoardStruct {
     /// All territories of the entire map
     pub territories: Vec<Rc<Territory>>,
     /// All territory IDs that are not yet claimed by a player
+    /// Neutral territories can never be claimed and are therefore never free
     pub free_territories: Vec<usize>,
     extra_info: RefCell<Vec<String>>,
     /// The maximum of extra info lines that are available
     /// Force prints the board when full and clears the extra lines afterwards
     extra_info_lines: usize,
     print_duration: Option<Duration>,
+    /// Draws the board when it is printed
+    renderer: RefCell<Box<dyn Renderer>>,
 }
 
 /// Provides a default implementation according to the standard ruleset
@@ -58,9 +65,14 @@ impl BoardStruct {
         print_duration: Option<Duration>,
     ) -> BoardStruct {
         continent::generate_ids(&continents);
+        let renderer: Box<dyn Renderer> = match board {
+            BoardType::ClassicBoard => Box::new(ClassicRenderer),
+            BoardType::Unimplemented => Box::new(ListRenderer),
+        };
         territory::generate_ids(&territories);
         let free_territories = territories
             .iter()
+            .filter(|territory| territory.neutral.borrow().is_none())
             .map(|territory| *territory.index.borrow())
             .collect::<Vec<usize>>();
 
@@ -78,6 +90,7 @@ impl BoardStruct {
             extra_info: RefCell::from(vec![]),
             extra_info_lines,
             print_duration,
+            renderer: RefCell::from(renderer),
         }
     }
 
@@ -90,6 +103,7 @@ impl BoardStruct {
         &mut self,
         free_territory_index: usize,
         player: Rc<dyn Player>,
+        seat: &Seat,
         verbose: bool,
     ) {
         // Territory lookup
@@ -99,10 +113,10 @@ impl BoardStruct {
         let state = player.get_state();
         // The player needs to have one army available to claim a territory
         assert!(
-            *state.armies.borrow() >= 1,
+            *seat.armies.borrow() >= 1,
             "The player should have at least 1 army in it's inventory. {} has {} remaining",
             state.name,
-            state.armies.borrow()
+            seat.armies.borrow()
         );
 
         // The player cannot claim a territory that is already occupied
@@ -117,11 +131,11 @@ impl BoardStruct {
         self.free_territories.remove(free_territory_index);
 
         // Place army
-        territory.place_armies(Rc::clone(&player), 1);
+        territory.place_armies(Rc::clone(&player), seat, 1);
 
         // Assign territory to player
         territory.set_player(Some(Rc::downgrade(&player)));
-        player.get_state().add_territory(Rc::clone(territory));
+        seat.add_territory(Rc::clone(territory));
 
         if verbose {
             self.set_extra_info(format!(
@@ -134,11 +148,9 @@ impl BoardStruct {
         // Assign part of continent to player
         let continent_index = *territory.continent.index.borrow();
         let continent = &self.continents[continent_index];
-        continent.territories_per_player.borrow_mut()[*player.get_state().index.borrow()] += 1;
-        if continent.territories_per_player.borrow()[*player.get_state().index.borrow()]
-            == continent.size
-        {
-            player.get_state().add_continent(Rc::clone(continent));
+        continent.territories_per_player.borrow_mut()[player.get_state().index()] += 1;
+        if continent.territories_per_player.borrow()[player.get_state().index()] == continent.size {
+            seat.add_continent(Rc::clone(continent));
 
             if verbose {
                 self.set_extra_info(format!(
@@ -169,15 +181,24 @@ impl BoardStruct {
         self.extra_info.borrow_mut().clear();
     }
 
+    /// Replaces the renderer that draws the board, e.g. by a `ListRenderer` for a plain list
+    pub fn set_renderer(&self, renderer: Box<dyn Renderer>) {
+        *self.renderer.borrow_mut() = renderer;
+    }
+
+    /// Renders the board with the extra info as text
+    pub fn render(&self) -> String {
+        self.renderer
+            .borrow()
+            .render(self, &self.extra_info.borrow())
+    }
+
     /// Prints the board to stdout
     /// If `None` Duration is specified waits on `enter` input key to continue
     pub fn print_board(&self) {
         // Clears the terminal
         print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-        match self.board {
-            BoardType::ClassicBoard => classic_board::print_board(self),
-            BoardType::Unimplemented => unimplemented!(),
-        }
+        print!("{}", self.render());
         if let Some(dur) = self.print_duration {
             thread::sleep(dur);
         } else {
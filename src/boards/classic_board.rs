@@ -4,9 +4,12 @@ use std::time::Duration;
 
 use colored::Colorize;
 
+use crate::boards::renderer::Renderer;
 use crate::boards::BoardStruct;
 use crate::boards::BoardType::ClassicBoard;
+use crate::commanders::Commander;
 use crate::continent::Continent;
+use crate::modifiers::Modifier;
 use crate::territory::Territory;
 
 pub fn new(players: usize, print_duration: Option<Duration>) -> BoardStruct {
@@ -77,6 +80,54 @@ pub fn new(players: usize, print_duration: Option<Duration>) -> BoardStruct {
     let western_australia = Rc::new(Territory::new("Western Australia", Rc::clone(&australia)));
     let eastern_australia = Rc::new(Territory::new("Eastern Australia", Rc::clone(&australia)));
 
+    // POSITIONS ON A MAP OF 1000 BY 600
+    for (territory, x, y) in [
+        (&alaska, 60, 80),
+        (&northwest_territory, 160, 80),
+        (&greenland, 330, 50),
+        (&alberta, 140, 150),
+        (&ontario, 220, 150),
+        (&quebec, 300, 150),
+        (&western_us, 150, 230),
+        (&eastern_us, 240, 240),
+        (&central_america, 170, 310),
+        (&venezuela, 230, 370),
+        (&brazil, 310, 430),
+        (&peru, 230, 450),
+        (&argentina, 250, 530),
+        (&iceland, 420, 90),
+        (&scandinavia, 510, 80),
+        (&great_britain, 420, 170),
+        (&northern_europe, 510, 170),
+        (&ukraine, 600, 130),
+        (&western_europe, 430, 250),
+        (&southern_europe, 520, 240),
+        (&yakutsk, 870, 60),
+        (&ural, 700, 120),
+        (&siberia, 780, 80),
+        (&irkutsk, 860, 140),
+        (&kamchatka, 950, 80),
+        (&afghanistan, 690, 210),
+        (&china, 820, 270),
+        (&mongolia, 870, 210),
+        (&japan, 960, 220),
+        (&middle_east, 630, 290),
+        (&india, 730, 310),
+        (&siam, 830, 350),
+        (&north_africa, 450, 340),
+        (&egypt, 540, 320),
+        (&congo, 530, 430),
+        (&east_africa, 600, 400),
+        (&south_africa, 540, 520),
+        (&madagascar, 640, 500),
+        (&indonesia, 840, 440),
+        (&new_guinea, 940, 430),
+        (&western_australia, 860, 530),
+        (&eastern_australia, 950, 520),
+    ] {
+        *territory.position.borrow_mut() = Some((x as f64, y as f64));
+    }
+
     // CONNECTIONS
     alaska.create_connections(vec![&northwest_territory, &alberta, &kamchatka]);
     northwest_territory.create_connections(vec![&alaska, &greenland, &alberta, &ontario]);
@@ -262,7 +313,55 @@ pub fn new(players: usize, print_duration: Option<Duration>) -> BoardStruct {
     )
 }
 
-pub fn print_board(board: &BoardStruct) {
+/// The surrounding stars of a territory, showing the initials of the commanders on it
+fn commander_stars(territory: &Territory) -> String {
+    let commanders = territory.commanders.borrow();
+    let initials: String = Commander::ALL
+        .iter()
+        .map(|commander| {
+            if commanders.contains(commander) {
+                commander.initial()
+            } else {
+                '*'
+            }
+        })
+        .collect();
+    format!("*{initials}*")
+}
+
+/// The name row of a territory.
+/// The left star marks a fortress `F` or a scar `X`, the right star marks a city `C`.
+fn name_row(territory: &Territory) -> String {
+    let modifiers = territory.modifiers.borrow();
+    let left = if modifiers.contains(&Modifier::Fortress) {
+        'F'
+    } else if modifiers.contains(&Modifier::Scar) {
+        'X'
+    } else {
+        '*'
+    };
+    let right = if modifiers
+        .iter()
+        .any(|modifier| matches!(modifier, Modifier::City(_)))
+    {
+        'C'
+    } else {
+        '*'
+    };
+    format!("{left} {:5} {right}", territory.abbr)
+}
+
+/// Draws the classic board as ASCII art, with a box for 6 lines of extra info
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClassicRenderer;
+
+impl Renderer for ClassicRenderer {
+    fn render(&self, board: &BoardStruct, info: &[String]) -> String {
+        render(board, info)
+    }
+}
+
+fn render(board: &BoardStruct, info: &[String]) -> String {
     // Armies on the territory
     let mut a = vec![];
     // Territory names
@@ -270,25 +369,32 @@ pub fn print_board(board: &BoardStruct) {
     // Surrounding star coloring
     let mut s = vec![];
     for territory in &board.territories {
+        let stars = commander_stars(territory);
+        let name = name_row(territory);
         let player = territory.get_player();
         if player.is_none() {
-            a.push(format!("* {:05} *", 0).white());
-            n.push(format!("* {:5} *", territory.abbr).white());
-            s.push("*******".white());
+            // Neutral territories show their armies, impassable territories are blocked
+            if territory.is_impassable() {
+                a.push(String::from("* ##### *").white());
+            } else {
+                a.push(format!("* {:05} *", territory.armies.borrow()).white());
+            }
+            n.push(name.white());
+            s.push(stars.white());
         } else if let Some(player) = territory.get_player() {
             let player = player.get_state();
             if *territory.armies.borrow() == 0 {
-                n.push(player.colorize(format!("* {:5} *", territory.abbr)).blink());
+                n.push(player.colorize(name).blink());
                 a.push(
                     player
                         .colorize(format!("* {:05} *", territory.armies.borrow()))
                         .blink(),
                 );
-                s.push(player.colorize(String::from("*******")).blink());
+                s.push(player.colorize(stars).blink());
             } else {
-                n.push(player.colorize(format!("* {:5} *", territory.abbr)));
+                n.push(player.colorize(name));
                 a.push(player.colorize(format!("* {:05} *", territory.armies.borrow())));
-                s.push(player.colorize(String::from("*******")));
+                s.push(player.colorize(stars));
             }
         }
     }
@@ -296,19 +402,19 @@ pub fn print_board(board: &BoardStruct) {
     // Extra info
     let mut i = vec![];
     for index in 0..6 {
-        let mut info = match board.extra_info.borrow().get(index) {
+        let mut line = match info.get(index) {
             Some(text) => String::from(text),
             None => String::from(""),
         };
 
-        if info.len() > 74 {
-            info.truncate(74);
-            info.replace_range(70..74, ".");
+        if line.len() > 74 {
+            line.truncate(74);
+            line.replace_range(70..74, ".");
         }
-        i.push(format!("{:<74}", info));
+        i.push(format!("{:<74}", line));
     }
 
-    print!(
+    format!(
         r"         +-----------------------------------------------------------------------------------------------------------------------------------------------------------------------+
          |                                                                                                                                                                       |
   # # #  |  # # # # # # # # # # # # # # # # # # #                                                                                                        # # # # # # # # # # #   |
@@ -0,0 +1,91 @@
+//! Renders a board as text for the terminal.
+//! Every board can be shown with the `ListRenderer`, boards with a map of their own,
+//! like the `ClassicRenderer`, draw it in ASCII art.
+use std::fmt::Debug;
+use std::rc::Weak;
+
+use crate::boards::BoardStruct;
+use crate::territory::ConnectionType;
+
+pub trait Renderer: Debug {
+    /// Renders the board together with the extra info lines
+    fn render(&self, board: &BoardStruct, info: &[String]) -> String;
+}
+
+/// Lists the territories of every continent with their owners and armies, for any board.
+/// Connections that are not over land are listed behind the territory they start from.
+/// The extra info follows below the list.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ListRenderer;
+
+impl Renderer for ListRenderer {
+    fn render(&self, board: &BoardStruct, info: &[String]) -> String {
+        let width = board
+            .territories
+            .iter()
+            .map(|territory| territory.name.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut text = String::new();
+        for continent in &board.continents {
+            text += &format!(
+                "{} (+{})\n",
+                continent.name,
+                continent.armies_reward.borrow()
+            );
+            for territory in board
+                .territories
+                .iter()
+                .filter(|territory| territory.continent == *continent)
+            {
+                let commanders: String = territory
+                    .commanders
+                    .borrow()
+                    .iter()
+                    .map(|commander| commander.initial())
+                    .collect();
+                let armies = format!("{:>5} {commanders}", territory.armies.borrow());
+                let line = match territory.get_player() {
+                    Some(player) => {
+                        let state = player.get_state();
+                        state
+                            .colorize(format!("{:<width$}  {armies}", territory.name))
+                            .to_string()
+                            + &format!("  {}", state.name)
+                    }
+                    None if territory.is_impassable() => {
+                        format!("{:<width$}  {:>5}  impassable", territory.name, "#")
+                    }
+                    None if territory.is_neutral() => {
+                        format!("{:<width$}  {armies}  neutral", territory.name)
+                    }
+                    None => format!("{:<width$}  {:>5}  free", territory.name, "-"),
+                };
+                let connections: Vec<String> = territory
+                    .connections
+                    .borrow()
+                    .iter()
+                    .filter_map(Weak::upgrade)
+                    .filter_map(|adjacent| match territory.connection_to(&adjacent) {
+                        Some(ConnectionType::Land) | None => None,
+                        Some(connection) => Some(format!("{connection} to {}", adjacent.name)),
+                    })
+                    .collect();
+                if connections.is_empty() {
+                    text += &format!("  {line}\n");
+                } else {
+                    text += &format!("  {line}  ({})\n", connections.join(", "));
+                }
+            }
+        }
+
+        if !info.is_empty() {
+            text += "\n";
+        }
+        for line in info {
+            text += &format!("{line}\n");
+        }
+        text
+    }
+}
@@ -0,0 +1,246 @@
+//! Draws a snapshot of a board as an SVG image, e.g. for reports and bug tickets.
+//!
+//! Every territory is a circle in the color of its owner, labeled with its armies and name,
+//! at the position given by the board definition. Boards without positions are laid out
+//! automatically, with the continents on a large circle and their territories on small circles.
+//! Connections are lines, dashed for sea connections and dotted for one-way connections,
+//! which end in an arrowhead at the territory they lead to.
+//! Every continent is a box around its territories.
+use std::f64::consts::TAU;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use colored::Color;
+
+use crate::boards::renderer::Renderer;
+use crate::boards::BoardStruct;
+use crate::territory::ConnectionType;
+
+/// The radius of a territory
+const RADIUS: f64 = 16.0;
+/// The space around the territories of a continent
+const PADDING: f64 = 32.0;
+
+/// Renders the board as an SVG image, with the extra info below the board
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SvgRenderer;
+
+impl Renderer for SvgRenderer {
+    fn render(&self, board: &BoardStruct, info: &[String]) -> String {
+        let positions = layout(board);
+        let width = positions.iter().map(|(x, _)| *x).fold(0.0, f64::max) + 2.0 * PADDING;
+        let height = positions.iter().map(|(_, y)| *y).fold(0.0, f64::max) + 2.0 * PADDING;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.0}\" height=\"{:.0}\" \
+             font-family=\"sans-serif\" font-size=\"10\" text-anchor=\"middle\">\n\
+             <defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+             markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\">\
+             <path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"#888888\"/></marker></defs>\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n",
+            height + 16.0 * info.len() as f64
+        );
+
+        for continent in &board.continents {
+            let territories: Vec<(f64, f64)> = board
+                .territories
+                .iter()
+                .filter(|territory| territory.continent == *continent)
+                .map(|territory| positions[*territory.index.borrow()])
+                .collect();
+            if territories.is_empty() {
+                continue;
+            }
+            let left = territories.iter().map(|(x, _)| *x).fold(f64::MAX, f64::min) - PADDING;
+            let top = territories.iter().map(|(_, y)| *y).fold(f64::MAX, f64::min) - PADDING;
+            let right = territories.iter().map(|(x, _)| *x).fold(f64::MIN, f64::max) + PADDING;
+            let bottom = territories.iter().map(|(_, y)| *y).fold(f64::MIN, f64::max) + PADDING;
+            svg += &format!(
+                "<g class=\"continent\"><rect x=\"{left:.1}\" y=\"{top:.1}\" width=\"{:.1}\" \
+                 height=\"{:.1}\" rx=\"12\" fill=\"#f4f4f4\" stroke=\"#aaaaaa\"/>\
+                 <text x=\"{left:.1}\" y=\"{:.1}\" text-anchor=\"start\" font-weight=\"bold\">\
+                 {} +{}</text></g>\n",
+                right - left,
+                bottom - top,
+                top + 12.0,
+                escape(&continent.name),
+                continent.armies_reward.borrow()
+            );
+        }
+
+        for territory in &board.territories {
+            let (x1, y1) = positions[*territory.index.borrow()];
+            for (adjacent, connection) in territory.connections.borrow().iter().zip(
+                territory
+                    .connection_types
+                    .borrow()
+                    .iter()
+                    .copied()
+                    .chain(std::iter::repeat(ConnectionType::Land)),
+            ) {
+                let Some(adjacent) = adjacent.upgrade() else {
+                    continue;
+                };
+                // Two-way connections are drawn once
+                let one_way = adjacent.connection_to(territory).is_none();
+                if !one_way && *adjacent.index.borrow() < *territory.index.borrow() {
+                    continue;
+                }
+                let (mut x2, mut y2) = positions[*adjacent.index.borrow()];
+                let style = if one_way {
+                    // The arrowhead stops at the edge of the territory instead of under it
+                    let length = (x2 - x1).hypot(y2 - y1);
+                    if length > RADIUS {
+                        x2 -= (x2 - x1) * RADIUS / length;
+                        y2 -= (y2 - y1) * RADIUS / length;
+                    }
+                    " stroke-dasharray=\"2 4\" marker-end=\"url(#arrow)\""
+                } else if connection == ConnectionType::Sea {
+                    " stroke-dasharray=\"6 4\""
+                } else {
+                    ""
+                };
+                svg += &format!(
+                    "<line x1=\"{x1:.1}\" y1=\"{y1:.1}\" x2=\"{x2:.1}\" y2=\"{y2:.1}\" \
+                     stroke=\"#888888\"{style}/>\n"
+                );
+            }
+        }
+
+        for territory in &board.territories {
+            let (x, y) = positions[*territory.index.borrow()];
+            let (fill, text, owner) = match territory.get_player() {
+                Some(player) => {
+                    let state = player.get_state();
+                    let (background, foreground) = state.colors();
+                    (color(background), color(foreground), state.name.clone())
+                }
+                None if territory.is_impassable() => (
+                    String::from("#555555"),
+                    String::from("#ffffff"),
+                    String::from("impassable"),
+                ),
+                None if territory.is_neutral() => (
+                    String::from("#bbbbbb"),
+                    String::from("#000000"),
+                    String::from("neutral"),
+                ),
+                None => (
+                    String::from("#ffffff"),
+                    String::from("#000000"),
+                    String::from("free"),
+                ),
+            };
+            let armies = if territory.is_impassable() {
+                String::from("#")
+            } else {
+                territory.armies.borrow().to_string()
+            };
+            svg += &format!(
+                "<g class=\"territory\"><title>{}: {}</title>\
+                 <circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"{RADIUS}\" fill=\"{fill}\" stroke=\"#333333\"/>\
+                 <text x=\"{x:.1}\" y=\"{:.1}\" fill=\"{text}\" font-size=\"12\">{armies}</text>\
+                 <text x=\"{x:.1}\" y=\"{:.1}\">{}</text></g>\n",
+                escape(&territory.name),
+                escape(&owner),
+                y + 4.0,
+                y + RADIUS + 11.0,
+                escape(&territory.name)
+            );
+        }
+
+        for (line, text) in info.iter().enumerate() {
+            svg += &format!(
+                "<text x=\"{PADDING}\" y=\"{:.1}\" text-anchor=\"start\" font-size=\"12\">{}</text>\n",
+                height + 16.0 * line as f64,
+                escape(text)
+            );
+        }
+        svg += "</svg>\n";
+        svg
+    }
+}
+
+/// Writes a snapshot of the board to an SVG file
+pub fn export(board: &BoardStruct, path: impl AsRef<Path>) -> io::Result<()> {
+    fs::write(path, SvgRenderer.render(board, &[]))
+}
+
+/// The positions of all territories, by index.
+/// Unless every territory has a position, all territories are laid out automatically.
+fn layout(board: &BoardStruct) -> Vec<(f64, f64)> {
+    let positions: Option<Vec<(f64, f64)>> = board
+        .territories
+        .iter()
+        .map(|territory| *territory.position.borrow())
+        .collect();
+    if let Some(positions) = positions {
+        return positions
+            .into_iter()
+            .map(|(x, y)| (x + PADDING, y + PADDING))
+            .collect();
+    }
+
+    // The continents are spread over a large circle, their territories over a small circle
+    let mut positions = vec![(0.0, 0.0); board.territories.len()];
+    let largest = board
+        .continents
+        .iter()
+        .map(|continent| continent.size)
+        .max()
+        .unwrap_or(1) as f64;
+    let small = (largest * 3.0 * RADIUS / TAU).max(2.0 * RADIUS);
+    let large = if board.continents.len() > 1 {
+        (board.continents.len() as f64 * 2.5 * (small + PADDING) / TAU).max(small + 2.0 * PADDING)
+    } else {
+        0.0
+    };
+    let center = large + small + 2.0 * PADDING;
+    for (index, continent) in board.continents.iter().enumerate() {
+        let angle = TAU * index as f64 / board.continents.len() as f64;
+        let (cx, cy) = (center + large * angle.cos(), center + large * angle.sin());
+        let territories: Vec<usize> = board
+            .territories
+            .iter()
+            .filter(|territory| territory.continent == *continent)
+            .map(|territory| *territory.index.borrow())
+            .collect();
+        for (place, territory) in territories.iter().enumerate() {
+            let angle = TAU * place as f64 / territories.len() as f64;
+            positions[*territory] = (cx + small * angle.cos(), cy + small * angle.sin());
+        }
+    }
+    positions
+}
+
+/// The colors of the `colored` crate as SVG colors
+fn color(color: Color) -> String {
+    let (r, g, b) = match color {
+        Color::Black => (0, 0, 0),
+        Color::Red => (205, 49, 49),
+        Color::Green => (13, 188, 121),
+        Color::Yellow => (229, 229, 16),
+        Color::Blue => (36, 114, 200),
+        Color::Magenta => (188, 63, 188),
+        Color::Cyan => (17, 168, 205),
+        Color::White => (229, 229, 229),
+        Color::BrightBlack => (102, 102, 102),
+        Color::BrightRed => (241, 76, 76),
+        Color::BrightGreen => (35, 209, 139),
+        Color::BrightYellow => (245, 245, 67),
+        Color::BrightBlue => (59, 142, 234),
+        Color::BrightMagenta => (214, 112, 214),
+        Color::BrightCyan => (41, 184, 219),
+        Color::BrightWhite => (255, 255, 255),
+        Color::TrueColor { r, g, b } => (r, g, b),
+    };
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Escapes text for XML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
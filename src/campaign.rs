@@ -0,0 +1,261 @@
+//! A campaign plays a sequence of games on the same board, as in Risk Legacy.
+//! The outcome of every game leaves permanent marks on the board,
+//! which are stored in a campaign file between runs.
+//!
+//! After every game:
+//! - the winner founds a city on one of its territories and names it
+//! - every continent the winner occupies entirely rewards one more army
+//! - the territory that was captured most often gets scarred
+//! - every other player starts the next game with one more army
+//!
+//! Players are recognized by their name, territories and continents by their index.
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::str::FromStr;
+use std::{fs, io};
+
+use crate::boards::BoardStruct;
+use crate::history::Event;
+use crate::modifiers::Modifier;
+use crate::Game;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Campaign {
+    /// The name of the winner of every game played so far, `None` if the game had no winner
+    pub games: Vec<Option<String>>,
+    /// The names given to territories
+    pub titles: BTreeMap<usize, String>,
+    /// The changed army rewards of continents
+    pub rewards: BTreeMap<usize, u32>,
+    /// The modifiers territories earned
+    pub modifiers: BTreeMap<usize, Vec<Modifier>>,
+    /// The extra armies players start a game with
+    pub bonuses: BTreeMap<String, u32>,
+}
+
+impl Campaign {
+    pub fn new() -> Self {
+        Campaign::default()
+    }
+
+    /// Loads the campaign from the given file, or starts a new campaign if there is no file yet
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => contents
+                .parse()
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Campaign::new()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Leaves the marks of the campaign on a freshly generated board.
+    /// Fails without leaving any mark when the campaign was played on a smaller board.
+    pub fn apply(&self, board: &BoardStruct) -> Result<(), String> {
+        let territories = self.titles.keys().chain(self.modifiers.keys());
+        if let Some(territory) = territories
+            .filter(|territory| **territory >= board.territories.len())
+            .max()
+        {
+            return Err(format!(
+                "The campaign marks territory {territory}, but the board has {} territories",
+                board.territories.len()
+            ));
+        }
+        if let Some(continent) = self
+            .rewards
+            .keys()
+            .filter(|continent| **continent >= board.continents.len())
+            .max()
+        {
+            return Err(format!(
+                "The campaign marks continent {continent}, but the board has {} continents",
+                board.continents.len()
+            ));
+        }
+
+        for (territory, title) in &self.titles {
+            *board.territories[*territory].title.borrow_mut() = Some(String::from(title));
+        }
+        for (continent, reward) in &self.rewards {
+            *board.continents[*continent].armies_reward.borrow_mut() = *reward;
+        }
+        for (territory, modifiers) in &self.modifiers {
+            board.territories[*territory]
+                .modifiers
+                .borrow_mut()
+                .extend(modifiers);
+        }
+        Ok(())
+    }
+
+    /// Hands out the starting bonuses to the players of a game that has been set up
+    pub fn grant_bonuses(&self, game: &Game) {
+        for (index, player) in game.get_players().iter().enumerate() {
+            if let Some(bonus) = self.bonuses.get(&player.get_state().name) {
+                game.grant_armies(index, *bonus);
+            }
+        }
+    }
+
+    /// Records the outcome of a finished game and leaves its marks on the campaign
+    pub fn record(&mut self, game: &Game) {
+        let board = game.get_board();
+        let winner = game.get_winner();
+
+        if let Some(winner) = winner {
+            let state = game.get_players()[winner].get_state();
+            let seat = &game.get_seats()[winner];
+
+            // The winner founds a city on its first territory that has not been named yet
+            let territories = seat.get_territories().borrow();
+            let mut territories: Vec<usize> = territories
+                .iter()
+                .map(|territory| *territory.index.borrow())
+                .collect();
+            territories.sort();
+            if let Some(territory) = territories
+                .into_iter()
+                .find(|territory| !self.titles.contains_key(territory))
+            {
+                self.modifiers
+                    .entry(territory)
+                    .or_default()
+                    .push(Modifier::City(1));
+                self.titles.insert(
+                    territory,
+                    format!("{} of {}", board.territories[territory].name, state.name),
+                );
+            }
+
+            for continent in seat.get_continents().borrow().iter() {
+                self.rewards.insert(
+                    *continent.index.borrow(),
+                    *continent.armies_reward.borrow() + 1,
+                );
+            }
+        }
+
+        // The most contested territory gets scarred
+        let mut captures = vec![0; board.territories.len()];
+        for event in game.get_history().borrow().iter() {
+            if let Event::Capture { territory, .. } = event {
+                captures[*territory] += 1;
+            }
+        }
+        if let Some((territory, _)) = captures
+            .iter()
+            .enumerate()
+            .filter(|(_, captures)| **captures > 0)
+            .max_by_key(|(territory, captures)| (**captures, std::cmp::Reverse(*territory)))
+        {
+            if !self.modifiers(territory).contains(&Modifier::Scar) {
+                self.modifiers
+                    .entry(territory)
+                    .or_default()
+                    .push(Modifier::Scar);
+            }
+        }
+
+        for (index, player) in game.get_players().iter().enumerate() {
+            if Some(index) != winner {
+                *self
+                    .bonuses
+                    .entry(String::from(&player.get_state().name))
+                    .or_default() += 1;
+            }
+        }
+
+        self.games
+            .push(winner.map(|winner| String::from(&game.get_players()[winner].get_state().name)));
+    }
+
+    /// The modifiers the given territory earned during the campaign
+    fn modifiers(&self, territory: usize) -> &[Modifier] {
+        self.modifiers
+            .get(&territory)
+            .map(|modifiers| modifiers.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+/// A campaign file has a single mark per line:
+/// ```text
+/// game <winner or ->
+/// title <territory> <name>
+/// reward <continent> <armies>
+/// modifier <territory> <modifier>
+/// bonus <armies> <player>
+/// ```
+impl Display for Campaign {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for winner in &self.games {
+            writeln!(f, "game {}", winner.as_deref().unwrap_or("-"))?;
+        }
+        for (territory, title) in &self.titles {
+            writeln!(f, "title {territory} {title}")?;
+        }
+        for (continent, reward) in &self.rewards {
+            writeln!(f, "reward {continent} {reward}")?;
+        }
+        for (territory, modifiers) in &self.modifiers {
+            for modifier in modifiers {
+                writeln!(f, "modifier {territory} {modifier}")?;
+            }
+        }
+        for (player, bonus) in &self.bonuses {
+            writeln!(f, "bonus {bonus} {player}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Campaign {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = |word: &str, line: &str| -> Result<usize, String> {
+            word.parse()
+                .map_err(|_| format!("Invalid number in campaign line: {line}"))
+        };
+
+        let mut campaign = Campaign::new();
+        for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let (first, second) = rest.split_once(' ').unwrap_or((rest, ""));
+            match kind {
+                "game" => campaign.games.push(match rest {
+                    "-" => None,
+                    winner => Some(String::from(winner)),
+                }),
+                "title" => {
+                    campaign
+                        .titles
+                        .insert(number(first, line)?, String::from(second));
+                }
+                "reward" => {
+                    campaign
+                        .rewards
+                        .insert(number(first, line)?, number(second, line)? as u32);
+                }
+                "modifier" => campaign
+                    .modifiers
+                    .entry(number(first, line)?)
+                    .or_default()
+                    .push(second.parse()?),
+                "bonus" => {
+                    campaign
+                        .bonuses
+                        .insert(String::from(second), number(first, line)? as u32);
+                }
+                _ => return Err(format!("Unknown campaign line: {line}")),
+            }
+        }
+        Ok(campaign)
+    }
+}
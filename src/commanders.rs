@@ -0,0 +1,66 @@
+//! Commanders are the special units of the Risk 2210 variant.
+//! Commanders are bought with energy and placed on a territory next to its armies.
+//! For every commander on a territory taking part in a battle,
+//! one of the dice rolled for that territory is an 8-sided die.
+//! Every player can have at most one commander of every kind on the board.
+//! Commanders are killed when the territory they are on gets captured.
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Commander {
+    Land,
+    Naval,
+    Diplomat,
+    Nuclear,
+    Space,
+}
+
+impl Commander {
+    /// All kinds of commanders
+    pub const ALL: [Commander; 5] = [
+        Commander::Land,
+        Commander::Naval,
+        Commander::Diplomat,
+        Commander::Nuclear,
+        Commander::Space,
+    ];
+
+    /// The energy it takes to buy the commander
+    pub fn cost(&self) -> u32 {
+        3
+    }
+
+    /// A single letter to show the commander on the board
+    pub fn initial(&self) -> char {
+        match self {
+            Commander::Land => 'L',
+            Commander::Naval => 'N',
+            Commander::Diplomat => 'D',
+            Commander::Nuclear => 'U',
+            Commander::Space => 'S',
+        }
+    }
+}
+
+impl Display for Commander {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Commander::Land => "Land",
+            Commander::Naval => "Naval",
+            Commander::Diplomat => "Diplomat",
+            Commander::Nuclear => "Nuclear",
+            Commander::Space => "Space",
+        };
+        write!(f, "{name} Commander")
+    }
+}
+
+/// The sides of the dice rolled for a territory with the given commanders.
+/// Every commander turns one of the dice into an 8-sided die.
+pub fn dice_sides(commanders: &[Commander], dice: u32) -> Vec<u32> {
+    (0..dice as usize)
+        .map(|i| if i < commanders.len() { 8 } else { 6 })
+        .collect()
+}
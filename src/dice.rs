@@ -1,9 +1,15 @@
 //! These functions below provide ways to roll dice
+//! and the dice sources used to resolve battles
 use crate::players::Player;
 use itertools::{enumerate, join};
 use rand::distributions::Uniform;
 use rand::Rng;
+use std::cell::RefCell;
+use std::cmp::min;
+use std::collections::VecDeque;
+use std::fmt::Debug;
 use std::rc::Rc;
+use std::sync::OnceLock;
 
 /// Simulates rolling a give amount of standard 6 sided dice
 pub fn roll_dice(amount: u32) -> Vec<u32> {
@@ -39,3 +45,318 @@ pub fn players_roll_die(players: &Vec<&Rc<dyn Player>>, verbose: bool) -> Vec<u3
     }
     rolls
 }
+
+/// The possible outcomes of a single roll with the given amount of attacking and defending dice.
+/// Every outcome consists of the losses of the attacker, the losses of the defender
+/// and the probability of the outcome.
+pub fn roll_outcomes(attacker_dice: u32, defender_dice: u32) -> Vec<Outcome> {
+    dice_outcomes(
+        &vec![6; attacker_dice as usize],
+        &vec![6; defender_dice as usize],
+    )
+}
+
+/// The possible outcomes of a single roll with dice of the given sides
+pub fn dice_outcomes(attacker_sides: &[u32], defender_sides: &[u32]) -> Vec<Outcome> {
+    let sides: Vec<u32> = attacker_sides
+        .iter()
+        .chain(defender_sides.iter())
+        .copied()
+        .collect();
+    let combinations = all_rolls(&sides);
+    let probability = 1.0 / combinations.len() as f64;
+
+    let mut outcomes: Vec<Outcome> = vec![];
+    for mut rolls in combinations {
+        let defender_rolls = rolls.split_off(attacker_sides.len());
+        let (attacker_losses, defender_losses) = compare_rolls(&rolls, &defender_rolls);
+
+        match outcomes
+            .iter_mut()
+            .find(|(a, d, _)| *a == attacker_losses && *d == defender_losses)
+        {
+            Some(outcome) => outcome.2 += probability,
+            None => outcomes.push((attacker_losses, defender_losses, probability)),
+        }
+    }
+    outcomes
+}
+
+/// Every possible roll of dice with the given sides
+fn all_rolls(sides: &[u32]) -> Vec<Vec<u32>> {
+    let total: u32 = sides.iter().product();
+    (0..total)
+        .map(|mut combination| {
+            sides
+                .iter()
+                .map(|side| {
+                    let roll = combination % side + 1;
+                    combination /= side;
+                    roll
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Rolls dice with the given sides
+pub fn roll_sides(sides: &[u32]) -> Vec<u32> {
+    let mut rng = rand::thread_rng();
+    sides.iter().map(|side| rng.gen_range(1..=*side)).collect()
+}
+
+/// The losses of the attacker, the losses of the defender and the probability of a roll
+type Outcome = (u32, u32, f64);
+
+/// The outcomes of every combination of up to 3 attacking and 2 defending dice
+static OUTCOMES: OnceLock<Vec<Vec<Vec<Outcome>>>> = OnceLock::new();
+
+/// The outcomes of a roll with up to 3 attacking and 2 defending dice, computed only once
+fn cached_outcomes(attacker_dice: u32, defender_dice: u32) -> &'static [Outcome] {
+    &OUTCOMES.get_or_init(|| {
+        (0..=3)
+            .map(|a| (0..=2).map(|d| roll_outcomes(a, d)).collect())
+            .collect()
+    })[attacker_dice as usize][defender_dice as usize]
+}
+
+/// The probability that `attackers` armies capture a territory defended by `defenders` armies.
+/// Both sides roll as many dice as possible until one side runs out of armies.
+pub fn capture_probability(attackers: u32, defenders: u32) -> f64 {
+    if defenders == 0 {
+        return 1.0;
+    }
+    if attackers == 0 {
+        return 0.0;
+    }
+
+    // probabilities[a][d] is the probability of capturing with a attackers against d defenders
+    let mut probabilities = vec![vec![0.0; defenders as usize + 1]; attackers as usize + 1];
+    for a in 0..=attackers as usize {
+        probabilities[a][0] = 1.0;
+        for d in 1..=defenders as usize {
+            if a == 0 {
+                continue;
+            }
+            probabilities[a][d] = cached_outcomes(min(3, a) as u32, min(2, d) as u32)
+                .iter()
+                .map(|(attacker_losses, defender_losses, p)| {
+                    p * probabilities[a - *attacker_losses as usize][d - *defender_losses as usize]
+                })
+                .sum();
+        }
+    }
+    probabilities[attackers as usize][defenders as usize]
+}
+
+/// A source of dice rolls used to resolve battles.
+/// The attacker rolls first, the defender rolls knowing the dice of the attacker.
+/// The highest dice of both sides are compared, the defender wins ties.
+/// Every die is given by its amount of sides, usually 6.
+pub trait DiceSource: Debug {
+    /// Rolls the given dice for the attacker
+    fn attacker_rolls(&self, sides: &[u32]) -> Vec<u32>;
+
+    /// Rolls the given dice for the defender
+    fn defender_rolls(&self, attacker_rolls: &[u32], sides: &[u32]) -> Vec<u32>;
+}
+
+/// Compares the highest dice of both sides.
+/// Returns the losses of the attacker and the defender.
+pub fn compare_rolls(attacker_rolls: &[u32], defender_rolls: &[u32]) -> (u32, u32) {
+    let mut attacker_rolls = attacker_rolls.to_vec();
+    let mut defender_rolls = defender_rolls.to_vec();
+    attacker_rolls.sort_by(|a, b| b.cmp(a));
+    defender_rolls.sort_by(|a, b| b.cmp(a));
+
+    let mut attacker_losses = 0;
+    let mut defender_losses = 0;
+    for (attacker, defender) in attacker_rolls.iter().zip(defender_rolls.iter()) {
+        if attacker > defender {
+            defender_losses += 1;
+        } else {
+            attacker_losses += 1;
+        }
+    }
+    (attacker_losses, defender_losses)
+}
+
+/// The expected losses of the defender when rolling against the given dice of the attacker
+fn expected_defender_losses(attacker_sides: &[u32], defender_sides: &[u32]) -> f64 {
+    let losses = |outcomes: &[Outcome]| -> f64 {
+        outcomes
+            .iter()
+            .map(|(_, defender_losses, p)| *defender_losses as f64 * p)
+            .sum()
+    };
+
+    if attacker_sides
+        .iter()
+        .chain(defender_sides)
+        .all(|side| *side == 6)
+    {
+        losses(cached_outcomes(
+            attacker_sides.len() as u32,
+            defender_sides.len() as u32,
+        ))
+    } else {
+        losses(&dice_outcomes(attacker_sides, defender_sides))
+    }
+}
+
+/// Independent, fair 6 sided dice
+#[derive(Debug, Default)]
+pub struct FairDice;
+
+impl DiceSource for FairDice {
+    fn attacker_rolls(&self, sides: &[u32]) -> Vec<u32> {
+        roll_sides(sides)
+    }
+
+    fn defender_rolls(&self, _attacker_rolls: &[u32], sides: &[u32]) -> Vec<u32> {
+        roll_sides(sides)
+    }
+}
+
+/// Dice that reduce streaky outcomes.
+/// The source keeps track of how lucky attackers have been compared to the expected losses.
+/// The rolls of the defender are biased to pull that luck back towards the expectation,
+/// the higher the `strength` the stronger the bias.
+#[derive(Debug)]
+pub struct BalancedDice {
+    strength: f64,
+    /// The defender losses that actually happened minus the expected defender losses
+    luck: RefCell<f64>,
+    /// The dice of the last roll of the attacker
+    attacker_sides: RefCell<Vec<u32>>,
+}
+
+impl BalancedDice {
+    pub fn new(strength: f64) -> Self {
+        BalancedDice {
+            strength,
+            luck: RefCell::from(0.0),
+            attacker_sides: RefCell::from(vec![]),
+        }
+    }
+}
+
+impl Default for BalancedDice {
+    fn default() -> Self {
+        BalancedDice::new(0.5)
+    }
+}
+
+impl DiceSource for BalancedDice {
+    fn attacker_rolls(&self, sides: &[u32]) -> Vec<u32> {
+        *self.attacker_sides.borrow_mut() = sides.to_vec();
+        roll_sides(sides)
+    }
+
+    /// Every possible roll of the defender is weighted by how much it restores the balance
+    fn defender_rolls(&self, attacker_rolls: &[u32], sides: &[u32]) -> Vec<u32> {
+        let luck = *self.luck.borrow();
+        let mut rolls = all_rolls(sides);
+        let weights: Vec<f64> = rolls
+            .iter()
+            .map(|roll| {
+                let (_, defender_losses) = compare_rolls(attacker_rolls, roll);
+                (-self.strength * luck * defender_losses as f64).exp()
+            })
+            .collect();
+
+        let mut sample = rand::thread_rng().gen::<f64>() * weights.iter().sum::<f64>();
+        let mut index = 0;
+        while index < weights.len() - 1 && sample >= weights[index] {
+            sample -= weights[index];
+            index += 1;
+        }
+
+        // The defender always rolls right after the attacker
+        let (_, defender_losses) = compare_rolls(attacker_rolls, &rolls[index]);
+        let attacker_sides = self.attacker_sides.borrow();
+        *self.luck.borrow_mut() +=
+            defender_losses as f64 - expected_defender_losses(&attacker_sides, sides);
+        rolls.swap_remove(index)
+    }
+}
+
+/// A deterministic source that makes every battle end in its expected losses.
+/// Fractional expected losses are accumulated and paid out once they add up to a whole army.
+/// The attacker always rolls 4s, the defender rolls 1s for the armies it loses
+/// and the highest side of its dice otherwise.
+/// The expectation of 8-sided dice of the attacker is not taken into account.
+#[derive(Debug, Default)]
+pub struct ExpectedDice {
+    expected: RefCell<f64>,
+    actual: RefCell<u32>,
+}
+
+impl DiceSource for ExpectedDice {
+    fn attacker_rolls(&self, sides: &[u32]) -> Vec<u32> {
+        vec![4; sides.len()]
+    }
+
+    fn defender_rolls(&self, attacker_rolls: &[u32], sides: &[u32]) -> Vec<u32> {
+        let compared = min(attacker_rolls.len(), sides.len()) as u32;
+        let attacker_sides = vec![6; attacker_rolls.len()];
+        *self.expected.borrow_mut() += expected_defender_losses(&attacker_sides, sides);
+
+        let owed = (self.expected.borrow().round() as u32).saturating_sub(*self.actual.borrow());
+        let defender_losses = min(compared, owed);
+        *self.actual.borrow_mut() += defender_losses;
+
+        sides
+            .iter()
+            .enumerate()
+            .map(|(i, side)| {
+                if (i as u32) < defender_losses {
+                    1
+                } else {
+                    *side
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns predetermined rolls in order, mainly useful for tests.
+/// Panics when it runs out of rolls.
+#[derive(Debug)]
+pub struct ScriptedDice {
+    rolls: RefCell<VecDeque<Vec<u32>>>,
+}
+
+impl ScriptedDice {
+    /// The rolls alternate between the attacker and the defender
+    pub fn new(rolls: Vec<Vec<u32>>) -> Self {
+        ScriptedDice {
+            rolls: RefCell::from(VecDeque::from(rolls)),
+        }
+    }
+
+    fn next(&self, sides: &[u32]) -> Vec<u32> {
+        let rolls = self
+            .rolls
+            .borrow_mut()
+            .pop_front()
+            .expect("Ran out of scripted rolls.");
+        assert!(
+            rolls.len() == sides.len() && rolls.iter().zip(sides).all(|(roll, side)| roll <= side),
+            "The scripted roll {:?} does not match the dice rolled {:?}.",
+            rolls,
+            sides
+        );
+        rolls
+    }
+}
+
+impl DiceSource for ScriptedDice {
+    fn attacker_rolls(&self, sides: &[u32]) -> Vec<u32> {
+        self.next(sides)
+    }
+
+    fn defender_rolls(&self, _attacker_rolls: &[u32], sides: &[u32]) -> Vec<u32> {
+        self.next(sides)
+    }
+}
@@ -0,0 +1,67 @@
+//! The history of a game.
+//! Every event gets recorded, in the order it happened.
+//! Under fog of war players only see the events they witnessed, see `GameView`.
+//! Players, territories and continents are referred to by their index.
+use serde::Serialize;
+
+use crate::commanders::Commander;
+use crate::modifiers::Modifier;
+use crate::rules::ForfeitReason;
+use crate::victory::Stalemate;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum Event {
+    /// A player claims a free territory during setup
+    Claim { player: usize, territory: usize },
+    /// A new round starts, every player that is not defeated takes a turn in the given order
+    Round { round: u64, order: Vec<usize> },
+    /// A player starts its turn
+    Turn { turn: u64, player: usize },
+    /// A player receives armies at the start of its turn
+    Reinforcement { player: usize, armies: u32 },
+    /// A player buys a commander and places it on a territory
+    Commander {
+        player: usize,
+        commander: Commander,
+        territory: usize,
+    },
+    /// The outcome of a single attack
+    Attack {
+        attacker: usize,
+        /// `None` when attacking a neutral territory
+        defender: Option<usize>,
+        /// The attacking territory
+        from: usize,
+        /// The defending territory
+        to: usize,
+        attacker_losses: u32,
+        defender_losses: u32,
+    },
+    /// A player captures a territory
+    Capture {
+        player: usize,
+        from: usize,
+        territory: usize,
+    },
+    /// A player moves armies between two of its territories at the end of its turn
+    Fortify {
+        player: usize,
+        from: usize,
+        to: usize,
+        armies: u32,
+    },
+    /// A territory earns a modifier during play
+    Modifier {
+        territory: usize,
+        modifier: Modifier,
+    },
+    /// A stalemate was detected at the end of a round
+    Stalemate { round: u64, stalemate: Stalemate },
+    /// A player has lost all of its territories
+    Defeat { player: usize, by: usize },
+    /// A player forfeits the game, its territories become neutral
+    Forfeit {
+        player: usize,
+        reason: ForfeitReason,
+    },
+}
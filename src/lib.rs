@@ -1,47 +1,216 @@
 use indicatif::{ProgressBar, ProgressStyle};
 use std::cell::RefCell;
 use std::cmp::{max, min};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::panic::{self, AssertUnwindSafe};
 use std::rc::Rc;
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 
-use itertools::enumerate;
+use itertools::{enumerate, join};
 
 use crate::boards::BoardStruct;
-use crate::dice::{player_rolls_dice, players_roll_die};
-use crate::players::Player;
-use crate::territory::Territory;
-
+use crate::commanders::Commander;
+use crate::dice::players_roll_die;
+use crate::history::Event;
+use crate::modifiers::Modifier;
+use crate::panics::{panic_message, Decision, DecisionTimeout, PanicFallback, PanicReport};
+use crate::players::{Player, Seat};
+use crate::rules::{Forfeit, ForfeitReason, Rules, TurnOrder};
+use crate::territory::Neutral;
+use crate::victory::{
+    GameResult, Stalemate, StalemateResponse, Tiebreak, Victory, VictoryCondition,
+};
+use crate::view::GameView;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+pub mod api;
 pub mod boards;
+pub mod campaign;
+pub mod commanders;
 pub mod continent;
 pub mod dice;
+pub mod history;
+pub mod missions;
+pub mod modifiers;
+pub mod panics;
 pub mod players;
+pub mod reinforcements;
+pub mod rules;
+pub mod server;
 pub mod territory;
+pub mod tui;
+pub mod victory;
+pub mod view;
 
+/// An attack from an occupied territory on an adjacent enemy territory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Attack {
-    dice: u32,
-    attacker: Rc<Territory>,
-    defender: Rc<Territory>,
+    /// The amount of dice the attacker rolls
+    pub dice: u32,
+    /// The index of the attacking territory
+    pub attacker: usize,
+    /// The index of the defending territory
+    pub defender: usize,
+    /// Resolve the entire battle in a single decision instead of a single roll of the dice
+    pub blitz: Option<Blitz>,
+    /// The dice rolled by the attacker.
+    /// Only shown to the defender when it gets to see the roll before deciding on its dice.
+    pub roll: Option<Vec<u32>>,
+}
+
+/// Decides when the engine stops rolling the dice during a blitz attack.
+/// A blitz always stops when the territory is captured or the attacker has a single army left.
+/// At least one roll of the dice happens for every attack.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Blitz {
+    /// Keep attacking until the territory is captured
+    UntilCaptured,
+    /// Stop when the attacking territory has the given amount of armies left
+    UntilArmiesLeft(u32),
+    /// Stop when the probability of capturing the territory drops below the given odds
+    UntilOdds(f64),
+}
+
+impl Display for Blitz {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Blitz::UntilCaptured => write!(f, "until captured"),
+            Blitz::UntilArmiesLeft(armies) => write!(f, "until {armies} armies are left"),
+            Blitz::UntilOdds(odds) => write!(f, "until the odds drop below {:.0}%", odds * 100.0),
+        }
+    }
+}
+
+/// How many dice the defender rolls during a blitz attack
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DefensePolicy {
+    /// Always roll as many dice as possible
+    MaxDice,
+    /// Roll the given amount of dice, one or two, or less if there are not enough armies
+    Dice(u32),
+    /// Ask the defender before every roll
+    PerRoll,
+}
+
+impl Display for DefensePolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DefensePolicy::MaxDice => write!(f, "with as many dice as possible"),
+            DefensePolicy::Dice(dice) => write!(f, "with {dice} dice"),
+            DefensePolicy::PerRoll => write!(f, "deciding on every roll"),
+        }
+    }
+}
+
+/// The phases of a single turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// The player receives and places its reinforcements, and buys commanders
+    Reinforce,
+    /// The player attacks until it decides to stop
+    Attack,
+    /// The player moves armies between its territories
+    Fortify,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Reinforce => write!(f, "reinforce"),
+            Phase::Attack => write!(f, "attack"),
+            Phase::Fortify => write!(f, "fortify"),
+        }
+    }
 }
 
 pub struct Game {
     players: Vec<Rc<dyn Player>>,
+    /// Everything the players hold, by player index
+    seats: Vec<Seat>,
     board: BoardStruct,
     defeated_players: usize,
     armies_in_box: RefCell<Vec<u32>>,
+    rules: Rules,
+    /// The index of the player that has won the game
+    winner: RefCell<Option<usize>>,
+    /// The ranked result, once the game is over
+    result: RefCell<Option<GameResult>>,
+    /// Whether every player held enough continents at the end of its last attack phase
+    holds_continents: Vec<bool>,
+    /// How many rounds ended in every position, by the hash of the position
+    positions: HashMap<u64, u32>,
+    /// The stalemates that have been detected so far
+    stalemates: Vec<Stalemate>,
+    /// The number of the current turn
+    turn: u64,
+    /// The number of the current round, every player that is not defeated takes a turn per round
+    round: u64,
+    /// The players that still take a turn in the current round
+    turn_queue: VecDeque<usize>,
+    /// The player whose turn it is, `None` between turns
+    current_player: Option<usize>,
+    /// The phase that is played next
+    phase: Phase,
+    /// The index of the player that starts every round, decided during setup
+    first_player: Option<usize>,
+    /// The order in which the players claimed their territories during setup
+    seat_order: Vec<usize>,
+    /// The time every player has taken for its decisions so far
+    thinking: RefCell<Vec<Duration>>,
+    /// The players that forfeit at the end of the current phase
+    forfeits: RefCell<Vec<(usize, ForfeitReason)>>,
+    /// The panics of players that have been caught so far
+    panics: RefCell<Vec<PanicReport>>,
+    history: RefCell<Vec<Event>>,
 }
 
 impl Game {
     pub fn new(players: Vec<Rc<dyn Player>>, board: BoardStruct) -> Game {
+        Game::with_rules(players, board, Rules::default())
+    }
+
+    /// Creates a game that is played with the given optional rules
+    pub fn with_rules(players: Vec<Rc<dyn Player>>, board: BoardStruct, rules: Rules) -> Game {
         let armies_in_box: Vec<u32> = players.iter().map(|_| 180).collect();
+        let holds_continents = vec![false; players.len()];
+        let thinking = vec![Duration::ZERO; players.len()];
+        let seats = players.iter().map(|_| Seat::new()).collect();
         players::generate_ids(&players);
         Game {
             players,
+            seats,
             board,
             defeated_players: 0,
             armies_in_box: RefCell::from(armies_in_box),
+            rules,
+            winner: RefCell::from(None),
+            result: RefCell::from(None),
+            holds_continents,
+            positions: HashMap::new(),
+            stalemates: vec![],
+            turn: 0,
+            round: 0,
+            turn_queue: VecDeque::new(),
+            current_player: None,
+            phase: Phase::Reinforce,
+            first_player: None,
+            seat_order: vec![],
+            thinking: RefCell::from(thinking),
+            forfeits: RefCell::from(vec![]),
+            panics: RefCell::from(vec![]),
+            history: RefCell::from(vec![]),
         }
     }
 
+    /// The index of the player that has won the game, if any
+    pub fn get_winner(&self) -> Option<usize> {
+        *self.winner.borrow()
+    }
+
     pub fn assert_enough_armies(&self, index: usize, armies: u32) {
         assert!(
             self.armies_in_box.borrow()[index] > 0,
@@ -51,6 +220,161 @@ impl Game {
         );
     }
 
+    pub fn get_players(&self) -> &Vec<Rc<dyn Player>> {
+        &self.players
+    }
+
+    /// Everything the players hold, by player index
+    pub fn get_seats(&self) -> &[Seat] {
+        &self.seats
+    }
+
+    /// Everything the given player holds
+    fn seat(&self, player: &Rc<dyn Player>) -> &Seat {
+        &self.seats[player.get_state().index()]
+    }
+
+    pub fn get_board(&self) -> &BoardStruct {
+        &self.board
+    }
+
+    pub fn get_rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// Moves armies from the box to the given player, on top of the armies it already has
+    pub fn grant_armies(&self, index: usize, armies: u32) {
+        let armies = min(armies, self.armies_in_box.borrow()[index]);
+        self.armies_in_box.borrow_mut()[index] -= armies;
+        *self.seats[index].armies.borrow_mut() += armies;
+    }
+
+    /// The panics of players that have been caught so far
+    pub fn get_panics(&self) -> &RefCell<Vec<PanicReport>> {
+        &self.panics
+    }
+
+    /// All events that happened so far
+    pub fn get_history(&self) -> &RefCell<Vec<Event>> {
+        &self.history
+    }
+
+    /// Adds a modifier earned during play to the given territory
+    pub fn add_modifier(&self, territory: usize, modifier: Modifier) {
+        self.board.territories[territory]
+            .modifiers
+            .borrow_mut()
+            .push(modifier);
+        self.history.borrow_mut().push(Event::Modifier {
+            territory,
+            modifier,
+        });
+    }
+
+    /// Lets the given player make a decision based on its view of the game.
+    /// `decision` validates what the player returns, so an invalid decision
+    /// panics and falls back the same way as a player that panics itself.
+    fn decide<T>(
+        &self,
+        player: &Rc<dyn Player>,
+        point: Decision,
+        decision: impl FnOnce(&GameView) -> T,
+        fallback: impl FnOnce() -> T,
+    ) -> T {
+        let index = player.get_state().index();
+        let start = Instant::now();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| self.view(player, decision)));
+        let elapsed = start.elapsed();
+        let result = match result {
+            Ok(result) => result,
+            Err(payload) => {
+                // A player that ran out of time forfeits like any other late player
+                let handling = if payload.is::<DecisionTimeout>() {
+                    PanicFallback::Forfeit
+                } else {
+                    self.rules.on_panic
+                };
+                self.panics.borrow_mut().push(PanicReport {
+                    player: index,
+                    turn: self.turn,
+                    decision: point,
+                    message: panic_message(payload.as_ref()),
+                    board: self
+                        .board
+                        .territories
+                        .iter()
+                        .map(|territory| {
+                            (
+                                territory
+                                    .get_player()
+                                    .map(|player| player.get_state().index()),
+                                *territory.armies.borrow(),
+                            )
+                        })
+                        .collect(),
+                    fallback: handling,
+                });
+                match handling {
+                    PanicFallback::Abort => panic::resume_unwind(payload),
+                    PanicFallback::Forfeit if payload.is::<DecisionTimeout>() => self
+                        .forfeits
+                        .borrow_mut()
+                        .push((index, ForfeitReason::DecisionTime)),
+                    PanicFallback::Forfeit => self
+                        .forfeits
+                        .borrow_mut()
+                        .push((index, ForfeitReason::Panic)),
+                    PanicFallback::Default => {}
+                }
+                fallback()
+            }
+        };
+
+        // A decision in this process cannot be interrupted,
+        // the player forfeits once it returns too late
+        let total = {
+            let mut thinking = self.thinking.borrow_mut();
+            thinking[index] += elapsed;
+            thinking[index]
+        };
+        let reason = if self
+            .rules
+            .decision_time
+            .is_some_and(|limit| elapsed > limit)
+        {
+            Some(ForfeitReason::DecisionTime)
+        } else if self.rules.game_time.is_some_and(|limit| total > limit) {
+            Some(ForfeitReason::GameTime)
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            let mut forfeits = self.forfeits.borrow_mut();
+            if !forfeits.iter().any(|(player, _)| *player == index) {
+                forfeits.push((index, reason));
+            }
+        }
+        result
+    }
+
+    /// Hands the view of the given player to the engine itself, without timing it
+    fn view<T>(&self, player: &Rc<dyn Player>, f: impl FnOnce(&GameView) -> T) -> T {
+        let history = self.history.borrow();
+        let view = GameView {
+            decision_time: self.rules.decision_time,
+            ..GameView::new(
+                &self.board,
+                &self.players,
+                &self.seats,
+                player.get_state().index(),
+                self.rules.fog_of_war,
+                self.turn,
+                &history,
+            )
+        };
+        f(&view)
+    }
+
     /// The game setup hands out the initial amount of armies to the players
     /// and lets the players claim their first territories
     /// `verbose` whether the function should output to stdout
@@ -59,6 +383,15 @@ impl Game {
             println!("--- SETUP ---\n");
         }
 
+        for player in &self.players {
+            self.decide(
+                player,
+                Decision::GameStart,
+                |game| player.game_start(game),
+                || (),
+            );
+        }
+
         // The total amount of armies a player is entitled to depends on the amount of players.
         // Playing with more than 6 players is not allowed
         let armies_per_player = vec![50, 35, 30, 25, 20][&self.players.len() - 2];
@@ -66,41 +399,141 @@ impl Game {
             println!("Armies per player: {}\n", armies_per_player);
         }
 
-        // Every player receives initial amount of armies
+        // Every player receives initial amount of armies, adjusted by its handicap
         for player in &*self.players {
             let state = player.get_state();
-            *state.armies.borrow_mut() = armies_per_player;
+            let handicap = self.rules.handicap(state.index());
+            let armies = (armies_per_player as i64 + handicap.starting_armies as i64).max(0) as u32;
+            *self.seat(player).armies.borrow_mut() = armies;
             assert!(
-                self.armies_in_box.borrow()[*state.index.borrow()] >= armies_per_player,
+                self.armies_in_box.borrow()[state.index()] >= armies,
                 "Not enough armies in the box"
             );
-            self.armies_in_box.borrow_mut()[*state.index.borrow()] -= armies_per_player;
+            self.armies_in_box.borrow_mut()[state.index()] -= armies;
+        }
+
+        // Every player is dealt a secret mission
+        if self.rules.secret_missions {
+            missions::deal_missions(
+                missions::generate_missions(&self.board, &self.players),
+                &self.seats,
+            );
+            if verbose {
+                println!("Every player has been dealt a secret mission.\n");
+            }
         }
 
         // Decide who gets to go first
-        if verbose {
+        if verbose && self.rules.turn_order == TurnOrder::DiceRoll {
             println!("Highest roller gets to place it's armies first!\n");
         }
-        let mut player = &self.players[first_player(&self.players.iter().collect(), verbose)];
+        let order = self.round_order(verbose);
+        self.seat_order = order.clone();
         if verbose {
-            println!("{} may begin!\n", player.get_state().name);
+            println!("{} may begin!\n", self.players[order[0]].get_state().name);
             self.board.print_board();
         }
 
+        // Players with a handicap claim their preassigned territories first
+        for player in &self.players {
+            let handicap = self.rules.handicap(player.get_state().index());
+            let mut territories = handicap.territories.clone();
+            for continent in &handicap.continents {
+                let continent = &self.board.continents[*continent];
+                territories.extend(
+                    self.board
+                        .territories
+                        .iter()
+                        .filter(|territory| territory.continent == *continent)
+                        .map(|territory| *territory.index.borrow()),
+                );
+            }
+
+            for territory in territories {
+                let free_territory_index = self
+                    .board
+                    .free_territories
+                    .iter()
+                    .position(|free| *free == territory);
+                assert!(
+                    free_territory_index.is_some(),
+                    "{} cannot be preassigned to {}, it is not free.",
+                    self.board.territories[territory].name,
+                    player.get_state().name
+                );
+                self.history.borrow_mut().push(Event::Claim {
+                    player: player.get_state().index(),
+                    territory,
+                });
+                self.board.claim_territory(
+                    free_territory_index.unwrap(),
+                    Rc::clone(player),
+                    &self.seats[player.get_state().index()],
+                    verbose,
+                );
+            }
+        }
+
+        // Claim the free territories in turn
+        let mut claims = 0;
         while !self.board.free_territories.is_empty() {
+            let mut seat = claims % order.len();
+            if self.rules.snake_claim && (claims / order.len()) % 2 == 1 {
+                seat = order.len() - 1 - seat;
+            }
+            let player = &self.players[order[seat]];
+            claims += 1;
+
             assert!(
-                self.armies_in_box.borrow()[*player.get_state().index.borrow()] > 0,
+                self.armies_in_box.borrow()[player.get_state().index()] > 0,
                 "Not enough armies in the box., Tried to take {} armies when only {} were available.",
                 1,
-                self.armies_in_box.borrow()[*player.get_state().index.borrow()]
+                self.armies_in_box.borrow()[player.get_state().index()]
             );
 
-            let free_territory_index = player.claim_territory(&self.board);
-            self.board
-                .claim_territory(free_territory_index, Rc::clone(player), verbose);
+            let free_territory_index = self.decide(
+                player,
+                Decision::Claim,
+                |game| {
+                    let free_territory_index = player.claim_territory(game);
+                    assert!(
+                        free_territory_index < self.board.free_territories.len(),
+                        "{} cannot claim free territory {}, only {} territories are free.",
+                        player.get_state().name,
+                        free_territory_index,
+                        self.board.free_territories.len()
+                    );
+                    free_territory_index
+                },
+                || 0,
+            );
+            self.history.borrow_mut().push(Event::Claim {
+                player: player.get_state().index(),
+                territory: self.board.free_territories[free_territory_index],
+            });
+            self.board.claim_territory(
+                free_territory_index,
+                Rc::clone(player),
+                &self.seats[player.get_state().index()],
+                verbose,
+            );
+        }
 
-            // Get the next player
-            player = &self.players[(&*player.get_state().index.borrow() + 1) % self.players.len()];
+        // The first territory every player claimed is its capital
+        if self
+            .rules
+            .victory_conditions
+            .contains(&VictoryCondition::Capitals)
+        {
+            for (index, seat) in enumerate(&self.seats) {
+                let capital = self.history.borrow().iter().find_map(|event| match event {
+                    Event::Claim { player, territory } if *player == index => {
+                        Some(Rc::clone(&self.board.territories[*territory]))
+                    }
+                    _ => None,
+                });
+                seat.set_capital(capital);
+            }
         }
 
         for continent in &self.board.continents {
@@ -109,7 +542,15 @@ impl Game {
                     .territories_per_player
                     .borrow()
                     .iter()
-                    .sum::<u32>(),
+                    .sum::<u32>()
+                    + self
+                        .board
+                        .territories
+                        .iter()
+                        .filter(|territory| {
+                            territory.is_neutral() && territory.continent == *continent
+                        })
+                        .count() as u32,
                 continent.size,
                 "{}",
                 continent
@@ -118,6 +559,8 @@ impl Game {
     }
 
     /// Starts the actual game and game loop
+    /// Plays turns until the game has a winner, `max_duration` has passed
+    /// or `max_turns` turns have been played.
     pub fn play(
         &mut self,
         max_duration: Option<Duration>,
@@ -125,10 +568,6 @@ impl Game {
         verbose: bool,
         with_progressbar: bool,
     ) {
-        let mut player = &self.players[first_player(&self.players.iter().collect(), verbose)];
-
-        let mut turn = 1;
-
         let start = SystemTime::now();
         let mut duration;
 
@@ -155,75 +594,767 @@ impl Game {
             }
 
             if let Some(turns) = max_turns {
-                if turn >= turns {
+                if self.turn >= turns {
                     break;
                 }
             }
 
-            if !*player.get_state().defeated.borrow() {
-                if verbose {
-                    self.board
-                        .set_extra_info(format!("TURN {turn}: {}", player.get_state().name));
-                    self.board.print_board();
-                    self.board.clear_extra_info();
-                }
+            if !self.step_turn(verbose) {
+                break;
+            }
 
-                self.army_accumulation(player, verbose);
+            if let Some(bar) = &progressbar {
+                bar.inc(1);
+            }
+        }
 
-                self.army_placement(player, verbose);
+        // Players are ranked by the tiebreak when the game was stopped at its limit
+        let result = self.tiebreak();
+        if verbose {
+            println!(
+                "{} ranks first: {}.",
+                self.players[result.first()].get_state().name,
+                result.victory
+            );
+        }
 
-                for defeated in self.attack(Rc::clone(player), verbose).iter() {
-                    *defeated.get_state().defeated.borrow_mut() = true;
-                    self.defeated_players += 1;
-                }
+        if let Some(bar) = progressbar {
+            bar.finish();
+        }
 
-                if self.defeated_players == (&self.players.len() - 1) {
-                    for territory in &self.board.territories {
-                        assert!(
-                            territory.get_player().is_some(),
-                            "Oops. Not all territories are occupied by the winner..."
-                        );
-                        assert_eq!(
-                            territory.get_player().unwrap().get_state(),
-                            player.get_state(),
-                            "Oops. Not all territories are occupied by the winner..."
-                        )
+        println!("Game took {} seconds.", duration.as_secs());
+        println!(
+            "Played {} turns in {} rounds in {} seconds.",
+            self.turn,
+            self.round,
+            duration.as_secs()
+        );
+        println!(
+            "Average turn took {} seconds.",
+            duration.as_secs() / max(1, self.turn)
+        );
+    }
+
+    /// The number of the current turn, 0 before the first turn
+    pub fn get_turn(&self) -> u64 {
+        self.turn
+    }
+
+    /// The number of the current round, 0 before the first round
+    pub fn get_round(&self) -> u64 {
+        self.round
+    }
+
+    /// The phase that is played next
+    pub fn get_phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// The index of the player whose turn it is, `None` between turns
+    pub fn get_current_player(&self) -> Option<usize> {
+        self.current_player
+    }
+
+    /// The players that still take a turn in the current round, in order
+    pub fn get_turn_queue(&self) -> Vec<usize> {
+        Vec::from(self.turn_queue.clone())
+    }
+
+    /// Whether the game has a winner or has been stopped with a tiebreak
+    pub fn is_over(&self) -> bool {
+        self.result.borrow().is_some()
+    }
+
+    /// The ranked result of the game, once the game is over
+    pub fn get_result(&self) -> Option<GameResult> {
+        self.result.borrow().clone()
+    }
+
+    /// Stops the game and ranks the players by the tiebreak of the rules,
+    /// unless the game is already over.
+    pub fn tiebreak(&self) -> GameResult {
+        if !self.is_over() {
+            self.declare(None, Victory::Tiebreak(self.rules.tiebreak));
+        }
+        self.get_result().unwrap()
+    }
+
+    /// Ends the game with the given winner, which is ranked first
+    fn declare(&self, winner: Option<usize>, victory: Victory) {
+        *self.winner.borrow_mut() = winner;
+        let result = GameResult {
+            ranking: self.rank(winner),
+            victory,
+            turn_order: self.seat_order.clone(),
+        };
+        *self.result.borrow_mut() = Some(result.clone());
+
+        for player in &self.players {
+            self.decide(
+                player,
+                Decision::GameOver,
+                |game| player.game_over(game, &result),
+                || (),
+            );
+        }
+    }
+
+    /// Ranks all players, starting with the given winner.
+    /// The players that are not defeated are ranked by the tiebreak of the rules,
+    /// followed by the defeated players, last defeated first.
+    fn rank(&self, winner: Option<usize>) -> Vec<usize> {
+        let mut alive: Vec<(usize, (u64, u64, u64))> = self
+            .players
+            .iter()
+            .filter(|player| !*self.seat(player).defeated.borrow())
+            .map(|player| player.get_state().index())
+            .filter(|index| Some(*index) != winner)
+            .map(|index| {
+                let seat = &self.seats[index];
+                let territories = seat.get_territories().borrow();
+                let armies = territories
+                    .iter()
+                    .map(|territory| *territory.armies.borrow() as u64)
+                    .sum::<u64>()
+                    + *seat.armies.borrow() as u64;
+                let primary = match self.rules.tiebreak {
+                    Tiebreak::Territories => territories.len() as u64,
+                    Tiebreak::Armies => armies,
+                    Tiebreak::Reinforcements => {
+                        let rewards = self.view(&self.players[index], |game| {
+                            self.rules.reinforcements.reinforcements(game)
+                        });
+                        let reward = rewards.iter().map(|(_, armies)| *armies).sum();
+                        self.rules.handicap(index).reinforcements(reward) as u64
                     }
+                };
+                (index, (primary, territories.len() as u64, armies))
+            })
+            .collect();
+        alive.sort_by_key(|(index, key)| (std::cmp::Reverse(*key), *index));
+
+        let defeated = self
+            .history
+            .borrow()
+            .iter()
+            .rev()
+            .filter_map(|event| match event {
+                Event::Defeat { player, .. } | Event::Forfeit { player, .. } => Some(*player),
+                _ => None,
+            })
+            .collect::<Vec<usize>>();
+
+        winner
+            .into_iter()
+            .chain(alive.into_iter().map(|(index, _)| index))
+            .chain(defeated)
+            .collect()
+    }
 
-                    if verbose {
-                        self.board.set_extra_info(format!(
-                            "{} HAS WON THE GAME!",
-                            player.get_state().name
-                        ));
+    /// Plays the remaining phases of the current turn, or an entire turn between turns.
+    /// Returns `false` when the game is over.
+    pub fn step_turn(&mut self, verbose: bool) -> bool {
+        loop {
+            if !self.step(verbose) {
+                return false;
+            }
+            if self.current_player.is_none() {
+                return true;
+            }
+        }
+    }
+
+    /// Plays a single phase of the current turn.
+    /// Between turns the next player in the turn queue starts its turn,
+    /// a new round starts when the queue is empty.
+    /// Returns `false` when the game is over.
+    pub fn step(&mut self, verbose: bool) -> bool {
+        if self.is_over() {
+            return false;
+        }
+
+        let player = match self.current_player {
+            Some(player) => Rc::clone(&self.players[player]),
+            None => {
+                // Stalemates are detected at the end of every round
+                if self.round > 0 && self.turn_queue.is_empty() && self.check_stalemate(verbose) {
+                    return false;
+                }
+
+                let player = self.start_turn(verbose);
+
+                // The player has held enough continents for a full round
+                let index = player.get_state().index();
+                if let Some(condition) = self.continents_condition(&player) {
+                    if self.holds_continents[index] {
+                        if verbose {
+                            self.board.set_extra_info(format!(
+                                "{} HAS HELD {} CONTINENTS FOR A FULL ROUND!",
+                                player.get_state().name,
+                                self.seat(&player).get_continents().borrow().len()
+                            ));
+                            self.board.print_board();
+                            self.board.clear_extra_info();
+                        }
+                        self.declare(Some(index), Victory::Condition(condition));
+                        return false;
                     }
+                }
+                player
+            }
+        };
+
+        match self.phase {
+            Phase::Reinforce => {
+                let index = player.get_state().index();
+                if self.decide(
+                    &player,
+                    Decision::Surrender,
+                    |game| player.surrender(game),
+                    || false,
+                ) {
+                    self.forfeits
+                        .borrow_mut()
+                        .push((index, ForfeitReason::Surrender));
+                    return !self.resolve_forfeits(verbose);
+                }
+                self.army_accumulation(&player, verbose);
+                self.army_placement(&player, verbose);
+                if self.rules.commanders {
+                    self.commander_purchase(&player, verbose);
+                }
+                self.phase = Phase::Attack;
+            }
+            Phase::Attack => {
+                for defeated in self.attack(Rc::clone(&player), verbose).iter() {
+                    *self.seat(defeated).defeated.borrow_mut() = true;
+                    self.defeated_players += 1;
 
-                    break;
+                    // Defeated players no longer take turns
+                    let index = defeated.get_state().index();
+                    self.turn_queue.retain(|queued| *queued != index);
+                }
+
+                if self.check_winner(&player, verbose) {
+                    return false;
                 }
+                let index = player.get_state().index();
+                self.holds_continents[index] = self.continents_condition(&player).is_some();
+                self.phase = Phase::Fortify;
+            }
+            Phase::Fortify => {
+                self.free_move(&player, verbose);
+                self.phase = Phase::Reinforce;
+                self.current_player = None;
+            }
+        }
+        !self.resolve_forfeits(verbose)
+    }
+
+    /// Lets the players that surrendered or ran out of time forfeit the game.
+    /// Returns `true` when the game is over.
+    fn resolve_forfeits(&mut self, verbose: bool) -> bool {
+        let forfeits: Vec<(usize, ForfeitReason)> = self.forfeits.borrow_mut().drain(..).collect();
+        for (index, reason) in forfeits {
+            self.forfeit(index, reason, verbose);
+        }
+
+        if !self.is_over() && self.defeated_players == self.players.len() - 1 {
+            let winner = self
+                .players
+                .iter()
+                .position(|player| !*self.seat(player).defeated.borrow())
+                .unwrap();
+            if verbose {
+                self.board.set_extra_info(format!(
+                    "{} HAS WON THE GAME!",
+                    self.players[winner].get_state().name
+                ));
+            }
+            self.declare(Some(winner), Victory::Domination);
+        }
+        self.is_over()
+    }
+
+    /// Whether the given player forfeits at the end of the current phase
+    fn is_forfeiting(&self, player: &Rc<dyn Player>) -> bool {
+        let index = player.get_state().index();
+        self.forfeits
+            .borrow()
+            .iter()
+            .any(|(forfeit, _)| *forfeit == index)
+    }
 
-                self.free_move(player, verbose);
+    /// Panics when the player places armies it does not have or on territories it does not occupy
+    fn validate_placement(&self, player: &Rc<dyn Player>, placement: &[(usize, u32)]) {
+        let state = player.get_state();
+        for (territory, _) in placement {
+            let territory = self.board.territories.get(*territory);
+            assert!(
+                territory.is_some_and(|territory| territory
+                    .get_player()
+                    .is_some_and(|occupant| occupant.get_state() == state)),
+                "{} can only place armies on territories it occupies.",
+                state.name
+            );
+        }
+        let armies: u32 = placement.iter().map(|(_, armies)| armies).sum();
+        assert!(
+            armies <= *self.seat(player).armies.borrow(),
+            "{} does not have {} armies available.",
+            state.name,
+            armies
+        );
+    }
 
-                if let Some(bar) = &progressbar {
-                    bar.inc(1);
+    /// Panics when the player cannot buy the given commanders
+    fn validate_purchases(&self, player: &Rc<dyn Player>, purchases: &[(Commander, usize)]) {
+        let state = player.get_state();
+        let seat = self.seat(player);
+        let mut energy = *seat.energy.borrow();
+        for (index, (commander, territory)) in purchases.iter().enumerate() {
+            let territory = self.board.territories.get(*territory);
+            assert!(
+                territory.is_some_and(|territory| territory
+                    .get_player()
+                    .is_some_and(|occupant| occupant.get_state() == state)),
+                "{} can only place a {} on a territory it occupies.",
+                state.name,
+                commander
+            );
+            assert!(
+                energy >= commander.cost(),
+                "{} does not have enough energy to buy a {}.",
+                state.name,
+                commander
+            );
+            energy -= commander.cost();
+            assert!(
+                !seat
+                    .get_territories()
+                    .borrow()
+                    .iter()
+                    .any(|territory| territory.commanders.borrow().contains(commander))
+                    && !purchases[..index]
+                        .iter()
+                        .any(|(bought, _)| bought == commander),
+                "{} already has a {} on the board.",
+                state.name,
+                commander
+            );
+        }
+    }
+
+    /// Panics when the player is not allowed to make the given attack
+    fn validate_attack(&self, player: &Rc<dyn Player>, attack: &Attack) {
+        let territories = &self.board.territories;
+        assert!(
+            attack.attacker < territories.len() && attack.defender < territories.len(),
+            "{} attacks a territory that does not exist.",
+            player.get_state().name
+        );
+        let attacking = &territories[attack.attacker];
+        let defending = &territories[attack.defender];
+
+        assert!(
+            attacking
+                .get_player()
+                .is_some_and(|aggressor| aggressor.get_state() == player.get_state()),
+            "The attacking territory is not claimed by {}",
+            player.get_state().name
+        );
+
+        assert!(0 < attack.dice && attack.dice < *attacking.armies.borrow(), "Number of dies should be between 1 and 3 and one less than the amount of armies on the territory.\narmies: {}\ndie: {}\n", *attacking.armies.borrow(), attack.dice);
+
+        assert!(defending.get_player().is_some() || defending.is_neutral());
+        assert!(
+            !defending.is_impassable(),
+            "{} is impassable and cannot be attacked.",
+            defending.name
+        );
+
+        if let Some(defender) = defending.get_player() {
+            assert_ne!(
+                player.get_state(),
+                defender.get_state(),
+                "You cannot attack a territory you own."
+            );
+        }
+
+        let connection = attacking.connection_to(defending);
+        assert!(
+            connection.is_some(),
+            "{} is not adjacent to {}.",
+            attacking.name,
+            defending.name
+        );
+        let connection = connection.unwrap();
+        assert!(
+            connection.allows_attack(),
+            "{} cannot attack {} over a {} connection.",
+            attacking.name,
+            defending.name,
+            connection
+        );
+        assert!(
+            attack.dice <= connection.max_attack_dice(),
+            "At most {} dice may be rolled over a {} connection.",
+            connection.max_attack_dice(),
+            connection
+        );
+    }
+
+    /// Panics when the player is not allowed to defend the given attack with the given dice.
+    /// The defender rolls at least one and at most two dice, but never more than its armies.
+    fn validate_defense(&self, player: &Rc<dyn Player>, attack: &Attack, dice: u32) {
+        let defending = &self.board.territories[attack.defender];
+        let armies = *defending.armies.borrow();
+        assert!(
+            0 < dice && dice <= min(2, armies),
+            "{} cannot defend {} with {} dice, it has {} armies.",
+            player.get_state().name,
+            defending.name,
+            dice,
+            armies
+        );
+    }
+
+    /// Panics when the player is not allowed to move the given armies at the end of its turn
+    fn validate_fortification(&self, player: &Rc<dyn Player>, from: usize, to: usize, armies: u32) {
+        let territories = &self.board.territories;
+        assert!(
+            from < territories.len() && to < territories.len(),
+            "{} fortifies a territory that does not exist.",
+            player.get_state().name
+        );
+        let source = &territories[from];
+        let target = &territories[to];
+        for territory in [source, target] {
+            assert!(
+                territory
+                    .get_player()
+                    .is_some_and(|owner| owner.get_state() == player.get_state()),
+                "{} is not claimed by {}",
+                territory.name,
+                player.get_state().name
+            );
+        }
+        assert!(
+            0 < armies && armies < *source.armies.borrow(),
+            "{} cannot move {} armies from {}, at least one of its {} armies stays behind.",
+            player.get_state().name,
+            armies,
+            source.name,
+            source.armies.borrow()
+        );
+
+        let connection = source.connection_to(target);
+        assert!(
+            connection.is_some(),
+            "{} is not adjacent to {}.",
+            source.name,
+            target.name
+        );
+        let connection = connection.unwrap();
+        assert!(
+            connection.allows_fortify(),
+            "{} cannot fortify {} over a {} connection.",
+            source.name,
+            target.name,
+            connection
+        );
+    }
+
+    /// Removes the given player from the game, its territories become neutral
+    fn forfeit(&mut self, index: usize, reason: ForfeitReason, verbose: bool) {
+        let player = Rc::clone(&self.players[index]);
+        let state = player.get_state();
+        let seat = &self.seats[index];
+        // Players can be defeated before their forfeit is resolved,
+        // and the last player left wins instead
+        if *seat.defeated.borrow() || self.defeated_players == self.players.len() - 1 {
+            return;
+        }
+        *seat.defeated.borrow_mut() = true;
+        self.defeated_players += 1;
+        self.turn_queue.retain(|queued| *queued != index);
+
+        for territory in seat.get_territories().borrow().iter() {
+            territory.continent.territories_per_player.borrow_mut()[index] -= 1;
+            territory.commanders.borrow_mut().clear();
+            territory.set_player(None);
+            if self.rules.forfeit == Forfeit::Remove {
+                self.armies_in_box.borrow_mut()[index] += *territory.armies.borrow() - 1;
+                *territory.armies.borrow_mut() = 1;
+            }
+            *territory.neutral.borrow_mut() = Some(Neutral {
+                armies: *territory.armies.borrow(),
+                impassable: false,
+            });
+        }
+        seat.get_territories().borrow_mut().clear();
+        seat.get_continents().borrow_mut().clear();
+
+        // Armies in reserve are returned to the box
+        self.armies_in_box.borrow_mut()[index] += *seat.armies.borrow();
+        *seat.armies.borrow_mut() = 0;
+
+        self.history.borrow_mut().push(Event::Forfeit {
+            player: index,
+            reason,
+        });
+
+        if verbose {
+            self.board
+                .set_extra_info(format!("{} {}!", state.name, reason).to_uppercase());
+            self.board.print_board();
+            self.board.clear_extra_info();
+        }
+
+        // The turn of the player ends immediately
+        if self.current_player == Some(index) {
+            self.current_player = None;
+            self.phase = Phase::Reinforce;
+        }
+    }
+
+    /// Lets the next player in the turn queue start its turn
+    fn start_turn(&mut self, verbose: bool) -> Rc<dyn Player> {
+        // Every round has its own order
+        if self.turn_queue.is_empty() {
+            self.round += 1;
+            self.turn_queue = VecDeque::from(self.round_order(verbose));
+            self.history.borrow_mut().push(Event::Round {
+                round: self.round,
+                order: self.get_turn_queue(),
+            });
+        }
+
+        let index = self.turn_queue.pop_front().unwrap();
+        let player = Rc::clone(&self.players[index]);
+        self.turn += 1;
+        self.current_player = Some(index);
+        self.phase = Phase::Reinforce;
+
+        if verbose {
+            self.board
+                .set_extra_info(format!("TURN {}: {}", self.turn, player.get_state().name));
+            self.board.print_board();
+            self.board.clear_extra_info();
+        }
+
+        self.history.borrow_mut().push(Event::Turn {
+            turn: self.turn,
+            player: index,
+        });
+        player
+    }
+
+    /// Detects the stalemates of the rules at the end of a round and responds to them.
+    /// Every kind of stalemate is only recorded once.
+    /// Returns whether the game is over.
+    fn check_stalemate(&mut self, verbose: bool) -> bool {
+        let rules = self.rules.stalemate;
+        let mut detected = vec![];
+
+        if let Some(rounds) = rules.rounds_without_capture {
+            let without_capture = self
+                .history
+                .borrow()
+                .iter()
+                .rev()
+                .take_while(|event| !matches!(event, Event::Capture { .. }))
+                .filter(|event| matches!(event, Event::Round { .. }))
+                .count() as u64;
+            if without_capture >= rounds {
+                detected.push(Stalemate::NoCaptures(rounds));
+            }
+        }
+
+        if rules.exhaustion && self.is_exhausted() {
+            detected.push(Stalemate::Exhaustion);
+        }
+
+        if let Some(repetitions) = rules.repetitions {
+            let mut hasher = DefaultHasher::new();
+            for territory in &self.board.territories {
+                territory
+                    .get_player()
+                    .map(|player| player.get_state().index())
+                    .hash(&mut hasher);
+                territory.armies.borrow().hash(&mut hasher);
+            }
+            let occurrences = self.positions.entry(hasher.finish()).or_default();
+            *occurrences += 1;
+            if *occurrences >= repetitions {
+                detected.push(Stalemate::RepeatedPosition(repetitions));
+            }
+        }
+
+        for stalemate in detected {
+            if self.stalemates.contains(&stalemate) {
+                continue;
+            }
+            self.stalemates.push(stalemate);
+            self.history.borrow_mut().push(Event::Stalemate {
+                round: self.round,
+                stalemate,
+            });
+
+            if verbose {
+                self.board.set_extra_info(format!("STALEMATE: {stalemate}"));
+            }
+
+            match rules.response {
+                StalemateResponse::Continue => {}
+                StalemateResponse::Draw => {
+                    self.declare(None, Victory::Draw(stalemate));
+                    return true;
                 }
+                StalemateResponse::Tiebreak => {
+                    self.declare(None, Victory::Stalemate(stalemate, self.rules.tiebreak));
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Whether all players that are not defeated ran out of armies and cannot attack
+    fn is_exhausted(&self) -> bool {
+        self.players
+            .iter()
+            .filter(|player| !*self.seat(player).defeated.borrow())
+            .all(|player| {
+                let state = player.get_state();
+                let seat = self.seat(player);
+                let out_of_armies =
+                    self.armies_in_box.borrow()[state.index()] == 0 && *seat.armies.borrow() == 0;
+                let can_attack = seat.get_territories().borrow().iter().any(|territory| {
+                    *territory.armies.borrow() >= 2
+                        && territory.connections.borrow().iter().any(|adjacent| {
+                            adjacent.upgrade().is_some_and(|adjacent| {
+                                !adjacent.is_impassable()
+                                    && adjacent.get_player().map(|owner| owner.get_state().index())
+                                        != Some(state.index())
+                                    && territory
+                                        .connection_to(&adjacent)
+                                        .is_some_and(|connection| connection.allows_attack())
+                            })
+                        })
+                });
+                out_of_armies && !can_attack
+            })
+    }
+
+    /// The continents victory condition, if the given player occupies enough continents for it
+    fn continents_condition(&self, player: &Rc<dyn Player>) -> Option<VictoryCondition> {
+        let continents = self.seat(player).get_continents().borrow().len() as u32;
+        self.rules
+            .victory_conditions
+            .iter()
+            .find(|condition| {
+                matches!(condition, VictoryCondition::Continents(required) if continents >= *required)
+            })
+            .copied()
+    }
+
+    /// The victory condition of the rules other than continents, if the given player fulfills it
+    fn fulfilled_condition(&self, player: &Rc<dyn Player>) -> Option<VictoryCondition> {
+        let seat = self.seat(player);
+        let territories = seat.get_territories().borrow().len() as f64;
+        // Impassable territories can never be occupied
+        let occupiable = self
+            .board
+            .territories
+            .iter()
+            .filter(|territory| !territory.is_impassable())
+            .count() as f64;
+        self.rules
+            .victory_conditions
+            .iter()
+            .find(|condition| match condition {
+                VictoryCondition::Territories(share) => territories >= share * occupiable,
+                VictoryCondition::Continents(_) => false,
+                VictoryCondition::Capitals => self.seats.iter().all(|other| {
+                    other
+                        .get_capital()
+                        .borrow()
+                        .as_ref()
+                        .is_some_and(|capital| {
+                            capital
+                                .get_player()
+                                .is_some_and(|owner| owner.get_state() == player.get_state())
+                        })
+                }),
+            })
+            .copied()
+    }
 
-                turn += 1;
+    /// Checks whether the game has been won after the attacks of the given player
+    fn check_winner(&self, player: &Rc<dyn Player>, verbose: bool) -> bool {
+        if let Some(winner) = self.get_winner() {
+            self.declare(Some(winner), Victory::Mission);
+            if verbose {
+                self.board.set_extra_info(format!(
+                    "{} HAS COMPLETED THE MISSION: {}",
+                    self.players[winner].get_state().name,
+                    self.seats[winner].get_mission().borrow().as_ref().unwrap()
+                ));
+                self.board.print_board();
+                self.board.clear_extra_info();
             }
 
-            // Get the next player
-            player = &self.players[(&*player.get_state().index.borrow() + 1) % self.players.len()];
+            return true;
         }
 
-        if let Some(bar) = progressbar {
-            bar.finish();
+        if self.defeated_players == (&self.players.len() - 1) {
+            // Neutral territories that were never conquered remain neutral
+            for territory in self
+                .board
+                .territories
+                .iter()
+                .filter(|territory| !territory.is_neutral())
+            {
+                assert!(
+                    territory.get_player().is_some(),
+                    "Oops. Not all territories are occupied by the winner..."
+                );
+                assert_eq!(
+                    territory.get_player().unwrap().get_state(),
+                    player.get_state(),
+                    "Oops. Not all territories are occupied by the winner..."
+                )
+            }
+
+            if verbose {
+                self.board
+                    .set_extra_info(format!("{} HAS WON THE GAME!", player.get_state().name));
+            }
+
+            self.declare(Some(player.get_state().index()), Victory::Domination);
+            return true;
         }
 
-        println!("Game took {} seconds.", duration.as_secs());
-        println!("Played {turn} turns in {} seconds.", duration.as_secs());
-        println!("Average turn took {} seconds.", duration.as_secs() / turn);
+        if let Some(condition) = self.fulfilled_condition(player) {
+            if verbose {
+                self.board.set_extra_info(format!(
+                    "{} HAS WON THE GAME BY {}!",
+                    player.get_state().name,
+                    condition.to_string().to_uppercase()
+                ));
+                self.board.print_board();
+                self.board.clear_extra_info();
+            }
+            self.declare(
+                Some(player.get_state().index()),
+                Victory::Condition(condition),
+            );
+            return true;
+        }
+        false
     }
 
-    /// Calculates how much armies a player is received upon starting its turn
-    /// A player is entitled to its amount of territories divided by 3 and reaches a minimum of 3 armies if possible
     fn army_accumulation(&self, player: &Rc<dyn Player>, verbose: bool) {
         if verbose {
             self.board
@@ -232,7 +1363,7 @@ impl Game {
         }
 
         // Out of armies
-        if self.armies_in_box.borrow()[*player.get_state().index.borrow()] == 0 {
+        if self.armies_in_box.borrow()[player.get_state().index()] == 0 {
             if verbose {
                 self.board
                     .set_extra_info(String::from("No more armies available in the box."));
@@ -243,45 +1374,78 @@ impl Game {
             return;
         }
 
-        let mut armies = min(
-            self.armies_in_box.borrow()[*player.get_state().index.borrow()],
-            max(
-                3,
-                player.get_state().get_territories().borrow().len() as u32 / 3,
-            ),
-        );
+        // Rewards of the reinforcement policy
+        let rewards = self.view(player, |game| {
+            self.rules.reinforcements.reinforcements(game)
+        });
+        let mut armies = 0;
+        for (reason, reward) in rewards {
+            let extra = min(
+                self.armies_in_box.borrow()[player.get_state().index()] - armies,
+                reward,
+            );
+            armies += extra;
 
-        if verbose {
-            self.board.set_extra_info(format!(
-                "{} receives {armies} for occupying {} territories.",
-                player.get_state().name,
-                player.get_state().get_territories().borrow().len()
-            ));
+            if verbose {
+                self.board.set_extra_info(format!(
+                    "{} receives {extra} armies for {reason}.",
+                    player.get_state().name
+                ));
+            }
         }
 
-        // Per continent rewards
-        for continent in player.get_state().get_continents().borrow().iter() {
+        // Per territory rewards, like cities
+        for territory in self.seat(player).get_territories().borrow().iter() {
+            let reward: u32 = territory
+                .modifiers
+                .borrow()
+                .iter()
+                .map(Modifier::reinforcements)
+                .sum();
+            if reward == 0 {
+                continue;
+            }
             let extra = min(
-                self.armies_in_box.borrow()[*player.get_state().index.borrow()] - armies,
-                continent.armies_reward,
+                self.armies_in_box.borrow()[player.get_state().index()] - armies,
+                reward,
             );
             armies += extra;
 
             if verbose {
                 self.board.set_extra_info(format!(
-                    "{} receives {extra} armies for occupying the entirety of {}.",
+                    "{} receives {extra} armies for occupying {}.",
                     player.get_state().name,
-                    continent.name
+                    territory.name
                 ));
             }
         }
 
+        // The handicap of the player
+        let handicap = self.rules.handicap(player.get_state().index());
+        let handicapped = min(
+            self.armies_in_box.borrow()[player.get_state().index()],
+            handicap.reinforcements(armies),
+        );
+        if verbose && handicapped != armies {
+            self.board.set_extra_info(format!(
+                "{} receives {:+} armies for its handicap.",
+                player.get_state().name,
+                handicapped as i64 - armies as i64
+            ));
+        }
+        let armies = handicapped;
+
         // Assign armies
-        *player.get_state().armies.borrow_mut() += armies;
+        *self.seat(player).armies.borrow_mut() += armies;
 
         // Remove assigned armies from the box
-        self.assert_enough_armies(*player.get_state().index.borrow(), armies);
-        self.armies_in_box.borrow_mut()[*player.get_state().index.borrow()] -= armies;
+        self.assert_enough_armies(player.get_state().index(), armies);
+        self.armies_in_box.borrow_mut()[player.get_state().index()] -= armies;
+
+        self.history.borrow_mut().push(Event::Reinforcement {
+            player: player.get_state().index(),
+            armies,
+        });
 
         if verbose {
             self.board.set_extra_info(format!(
@@ -300,7 +1464,16 @@ impl Game {
             self.board.set_extra_info(String::from(""));
         }
 
-        let placement = player.place_armies(&self.board);
+        let placement = self.decide(
+            player,
+            Decision::Placement,
+            |game| {
+                let placement = player.place_armies(game);
+                self.validate_placement(player, &placement);
+                placement
+            },
+            Vec::new,
+        );
 
         if placement.is_empty() {
             if verbose {
@@ -317,7 +1490,8 @@ impl Game {
         }
 
         for (territory, armies) in placement.iter() {
-            territory.place_armies(Rc::clone(player), *armies);
+            let territory = &self.board.territories[*territory];
+            territory.place_armies(Rc::clone(player), self.seat(player), *armies);
             if verbose {
                 self.board
                     .set_extra_info(format!(" * {} +{armies}", territory.name));
@@ -330,127 +1504,225 @@ impl Game {
         }
     }
 
-    /// Attacking phase
-    /// Returns a list of defeated players
-    fn attack(&self, player: Rc<dyn Player>, verbose: bool) -> Vec<Rc<dyn Player>> {
-        let mut defeated = vec![];
+    /// Hands out energy to the player and lets the player buy commanders with it.
+    /// A player earns as much energy as it is entitled to armies for its territories.
+    fn commander_purchase(&self, player: &Rc<dyn Player>, verbose: bool) {
+        let state = player.get_state();
+        let seat = self.seat(player);
+        let energy = max(3, seat.get_territories().borrow().len() as u32 / 3);
+        *seat.energy.borrow_mut() += energy;
 
-        while let Some(attack) = player.attack(&self.board) {
-            assert!(attack.attacker.get_player().is_some());
-
-            // The player who attacks
-            let aggressor = attack.attacker.get_player().unwrap();
+        if verbose {
+            self.board
+                .set_extra_info(String::from("Commander Purchase:"));
+            self.board.set_extra_info(String::from(""));
+            self.board.set_extra_info(format!(
+                "{} receives {energy} energy and has {} energy available.",
+                state.name,
+                seat.energy.borrow()
+            ));
+        }
 
-            assert_eq!(
-                aggressor.get_state(),
-                player.get_state(),
-                "The attacking territory is not claimed by {}",
-                player.get_state().name
-            );
+        let purchases = self.decide(
+            player,
+            Decision::Commanders,
+            |game| {
+                let purchases = player.buy_commanders(game);
+                self.validate_purchases(player, &purchases);
+                purchases
+            },
+            Vec::new,
+        );
+        for (commander, territory) in purchases {
+            let territory = &self.board.territories[territory];
+            *seat.energy.borrow_mut() -= commander.cost();
+            territory.commanders.borrow_mut().push(commander);
+            self.history.borrow_mut().push(Event::Commander {
+                player: state.index(),
+                commander,
+                territory: *territory.index.borrow(),
+            });
 
-            assert!(0 < attack.dice && attack.dice < *attack.attacker.armies.borrow(), "Number of dies should be between 1 and 3 and one less than the amount of armies on the territory.\narmies: {}\ndie: {}\n", *attack.attacker.armies.borrow(), attack.dice);
+            if verbose {
+                self.board
+                    .set_extra_info(format!(" * {} on {}", commander, territory.name));
+            }
+        }
 
-            assert!(attack.defender.get_player().is_some());
+        if verbose {
+            self.board.print_board();
+            self.board.clear_extra_info();
+        }
+    }
 
-            let defender = attack.defender.get_player().unwrap();
+    /// Attacking phase
+    /// Returns a list of defeated players
+    fn attack(&self, player: Rc<dyn Player>, verbose: bool) -> Vec<Rc<dyn Player>> {
+        let mut defeated = vec![];
 
-            assert_ne!(
-                aggressor.get_state(),
-                defender.get_state(),
-                "You cannot attack a territory you own."
-            );
+        while let Some(attack) = self.decide(
+            &player,
+            Decision::Attack,
+            |game| {
+                let attack = player.attack(game);
+                if let Some(attack) = &attack {
+                    self.validate_attack(&player, attack);
+                }
+                attack
+            },
+            || None,
+        ) {
+            // A player that is about to forfeit no longer attacks
+            if self.is_forfeiting(&player) {
+                break;
+            }
 
-            let defense = defender.defend(&attack);
-            assert!(
-                0 < defense && defense <= max(2, *aggressor.get_state().armies.borrow()),
-                "Incorrect number of dice used by the defender."
-            );
+            let attacking = Rc::clone(&self.board.territories[attack.attacker]);
+            let defending = Rc::clone(&self.board.territories[attack.defender]);
 
-            if verbose {
-                self.board.set_extra_info(format!(
-                    "{} attacks {} with {} armies",
-                    attack.attacker.name, attack.defender.name, attack.dice
-                ));
-                self.board.set_extra_info(String::from(""));
-                self.board.set_extra_info(format!(
-                    "{} defends with {} armies",
-                    attack.defender.name, defense
-                ));
-                self.board.set_extra_info(String::from(""));
-            }
+            // The player who attacks
+            let aggressor = attacking.get_player().unwrap();
 
-            // Simulate dice rolls
-            let mut attacker_rolls = player_rolls_dice(aggressor.as_ref(), attack.dice, false);
-            let mut defender_rolls = player_rolls_dice(defender.as_ref(), defense, false);
+            // The player who defends, `None` for neutral territories
+            let defender = defending.get_player();
 
-            attacker_rolls.sort();
-            defender_rolls.sort();
+            let (attacker_losses, defender_losses, dice) = match attack.blitz {
+                None => {
+                    if verbose {
+                        self.board.set_extra_info(format!(
+                            "{} attacks {} with {} armies",
+                            attacking.name, defending.name, attack.dice
+                        ));
+                    }
 
-            let mut attacker_losses = 0;
-            let mut defender_losses = 0;
-            for i in 0..min(attacker_rolls.len(), defender_rolls.len()) {
-                if attacker_rolls[i] > defender_rolls[i] {
-                    defender_losses += 1;
-                } else {
-                    attacker_losses += 1;
+                    let (attacker_losses, defender_losses) =
+                        self.battle(&attack, verbose, |attack| {
+                            // Neutral armies always defend with as many dice as possible
+                            let Some(defender) = &defender else {
+                                return min(2, *defending.armies.borrow());
+                            };
+                            self.decide(
+                                defender,
+                                Decision::Defense,
+                                |game| {
+                                    let dice = defender.defend(game, attack);
+                                    self.validate_defense(defender, attack, dice);
+                                    dice
+                                },
+                                || min(2, *defending.armies.borrow()),
+                            )
+                        });
+                    (attacker_losses, defender_losses, attack.dice)
                 }
-            }
+                Some(blitz) => {
+                    let policy = match &defender {
+                        Some(defender) => self.decide(
+                            defender,
+                            Decision::BlitzDefense,
+                            |game| {
+                                let policy = defender.defend_blitz(game, &attack);
+                                if let DefensePolicy::Dice(dice) = policy {
+                                    assert!(
+                                        (1..=2).contains(&dice),
+                                        "{} cannot defend with {} dice, only with one or two.",
+                                        defender.get_state().name,
+                                        dice
+                                    );
+                                }
+                                policy
+                            },
+                            || DefensePolicy::MaxDice,
+                        ),
+                        None => DefensePolicy::MaxDice,
+                    };
 
-            // Remove killed armies
-            *attack.attacker.armies.borrow_mut() -= attacker_losses;
-            *attack.defender.armies.borrow_mut() -= defender_losses;
+                    if verbose {
+                        self.board.set_extra_info(format!(
+                            "{} blitzes {} with up to {} dice ({})",
+                            attacking.name, defending.name, attack.dice, blitz
+                        ));
+                        self.board.set_extra_info(String::from(""));
+                        self.board
+                            .set_extra_info(format!("{} defends {}", defending.name, policy));
+                        self.board.set_extra_info(String::from(""));
+                    }
 
-            // Add killed armies back into the box
-            self.armies_in_box.borrow_mut()[*aggressor.get_state().index.borrow()] +=
-                attacker_losses;
-            self.armies_in_box.borrow_mut()[*defender.get_state().index.borrow()] +=
-                defender_losses;
+                    self.blitz(&attack, blitz, policy)
+                }
+            };
+
+            self.history.borrow_mut().push(Event::Attack {
+                attacker: aggressor.get_state().index(),
+                defender: defender
+                    .as_ref()
+                    .map(|defender| defender.get_state().index()),
+                from: attack.attacker,
+                to: attack.defender,
+                attacker_losses,
+                defender_losses,
+            });
 
             if verbose {
                 self.board.set_extra_info(format!(
                     "Attacker lost {attacker_losses} armies. {} remaining on {}.",
-                    *attack.attacker.armies.borrow(),
-                    attack.attacker.name
+                    *attacking.armies.borrow(),
+                    attacking.name
                 ));
 
                 self.board.set_extra_info(format!(
                     "Defender lost {defender_losses} armies. {} remaining on {}.",
-                    *attack.defender.armies.borrow(),
-                    attack.defender.name
+                    *defending.armies.borrow(),
+                    defending.name
                 ));
             }
 
             // The defender loses the territory
-            if *attack.defender.armies.borrow() == 0 {
+            if *defending.armies.borrow() == 0 {
                 if verbose {
                     self.board.set_extra_info(format!(
                         "{} has defeated all armies and captures {}.",
-                        attack.attacker.name, attack.defender.name
+                        attacking.name, defending.name
                     ));
                 }
 
-                // The defender loses a continent
-                if defender
-                    .get_state()
-                    .get_continents()
-                    .borrow()
-                    .contains(&attack.defender.continent)
-                {
-                    defender
-                        .get_state()
-                        .remove_continent(&attack.defender.continent);
-                }
-
-                defender.get_state().remove_territory(&attack.defender);
-                aggressor
-                    .get_state()
-                    .add_territory(Rc::clone(&attack.defender));
+                if let Some(defender) = &defender {
+                    // The defender loses a continent
+                    if self
+                        .seat(defender)
+                        .get_continents()
+                        .borrow()
+                        .contains(&defending.continent)
+                    {
+                        self.seat(defender).remove_continent(&defending.continent);
+                    }
 
-                attack.defender.set_player(Some(Rc::downgrade(&aggressor)));
+                    self.seat(defender).remove_territory(&defending);
+                }
 
-                let capture = aggressor.capture(&attack);
-                assert!(capture >= attack.dice, "You must move into the territory with at least as many armies as the number of dice rolled.");
-                assert!(capture < *attack.attacker.armies.borrow(), "Not enough armies on territory. No territory may ever be left unoccupied at any time during the game.");
+                // Commanders on the captured territory are killed
+                defending.commanders.borrow_mut().clear();
+
+                self.seat(&aggressor).add_territory(Rc::clone(&defending));
+
+                defending.set_player(Some(Rc::downgrade(&aggressor)));
+
+                // The attack as it was rolled last
+                let attack = Attack {
+                    dice,
+                    roll: None,
+                    ..attack
+                };
+                let capture = self.decide(
+                    &aggressor,
+                    Decision::Capture,
+                    |game| {
+                        let capture = aggressor.capture(game, &attack);
+                        assert!(capture >= dice, "You must move into the territory with at least as many armies as the number of dice rolled.");
+                        assert!(capture < *attacking.armies.borrow(), "Not enough armies on territory. No territory may ever be left unoccupied at any time during the game.");
+                        capture
+                    },
+                    || dice,
+                );
 
                 if verbose {
                     self.board.print_board();
@@ -458,41 +1730,35 @@ impl Game {
                 }
 
                 // Move armies from the attacking territory to the captured territory
-                *attack.defender.armies.borrow_mut() = capture;
-                *attack.attacker.armies.borrow_mut() -= capture;
-
-                if attack.defender.continent.territories_per_player.borrow()
-                    [*defender.get_state().index.borrow()]
-                    == 0
-                {
-                    println!("{}", attack.defender.continent);
-                    println!("{}", defender.get_state());
+                *defending.armies.borrow_mut() = capture;
+                *attacking.armies.borrow_mut() -= capture;
+
+                self.history.borrow_mut().push(Event::Capture {
+                    player: aggressor.get_state().index(),
+                    from: attack.attacker,
+                    territory: attack.defender,
+                });
+
+                if let Some(defender) = &defender {
+                    defending.continent.territories_per_player.borrow_mut()
+                        [defender.get_state().index()] -= 1;
                 }
-                attack
-                    .defender
-                    .continent
-                    .territories_per_player
-                    .borrow_mut()[*defender.get_state().index.borrow()] -= 1;
-                attack
-                    .defender
-                    .continent
-                    .territories_per_player
-                    .borrow_mut()[*aggressor.get_state().index.borrow()] += 1;
+                defending.continent.territories_per_player.borrow_mut()
+                    [aggressor.get_state().index()] += 1;
 
                 // The attacker captures an entire continent
-                if attack.defender.continent.territories_per_player.borrow()
-                    [*aggressor.get_state().index.borrow()]
-                    == attack.defender.continent.size
+                if defending.continent.territories_per_player.borrow()
+                    [aggressor.get_state().index()]
+                    == defending.continent.size
                 {
-                    aggressor
-                        .get_state()
-                        .add_continent(Rc::clone(&attack.defender.continent));
+                    self.seat(&aggressor)
+                        .add_continent(Rc::clone(&defending.continent));
 
                     if verbose {
                         self.board.set_extra_info(format!(
                             "{} has taken over the entirety of {}",
                             aggressor.get_state().name,
-                            attack.defender.continent.name
+                            defending.continent.name
                         ));
                         self.board.print_board();
                         self.board.clear_extra_info();
@@ -500,9 +1766,18 @@ impl Game {
                 }
 
                 // The defender has no more territories and is thus defeated
-                if defender.get_state().get_territories().borrow().is_empty() {
+                if let Some(defender) = defender
+                    .as_ref()
+                    .filter(|defender| self.seat(defender).get_territories().borrow().is_empty())
+                {
                     // Remove the defeated player from the list of players
-                    defeated.push(Rc::clone(&defender));
+                    defeated.push(Rc::clone(defender));
+                    *self.seat(defender).defeated_by.borrow_mut() =
+                        Some(aggressor.get_state().index());
+                    self.history.borrow_mut().push(Event::Defeat {
+                        player: defender.get_state().index(),
+                        by: aggressor.get_state().index(),
+                    });
 
                     if verbose {
                         self.board.set_extra_info(format!(
@@ -514,11 +1789,19 @@ impl Game {
                         self.board.clear_extra_info();
                     }
                 }
+
+                // Objectives are checked after every capture
+                if self.rules.secret_missions {
+                    if let Some(winner) = self.fulfilled_mission() {
+                        *self.winner.borrow_mut() = Some(winner);
+                        break;
+                    }
+                }
             } else if verbose {
                 self.board.set_extra_info(format!(
                     "{} was not able to take {}.",
                     aggressor.get_state().name,
-                    attack.defender.name
+                    defending.name
                 ));
             }
 
@@ -530,8 +1813,222 @@ impl Game {
         defeated
     }
 
-    fn free_move(&self, _player: &Rc<dyn Player>, _verbose: bool) {
-        // TODO: Implement free move
+    /// Rolls the dice for a single battle of the given attack and removes the killed armies.
+    /// The attacker rolls first, `defense` decides how many dice the defender rolls,
+    /// which is validated before it gets here.
+    /// Depending on the rules, the defender gets to see the roll of the attacker.
+    /// Returns the losses of the attacker and the defender.
+    fn battle(
+        &self,
+        attack: &Attack,
+        verbose: bool,
+        defense: impl FnOnce(&Attack) -> u32,
+    ) -> (u32, u32) {
+        let attacking = &self.board.territories[attack.attacker];
+        let defending = &self.board.territories[attack.defender];
+        let aggressor = attacking.get_player().unwrap();
+        let defender = defending.get_player();
+
+        // Roll the dice
+        let attacker_sides = commanders::dice_sides(&attacking.commanders.borrow(), attack.dice);
+        let attacker_rolls = self.rules.dice.attacker_rolls(&attacker_sides);
+        let attack = Attack {
+            roll: if self.rules.defender_sees_roll {
+                Some(attacker_rolls.clone())
+            } else {
+                None
+            },
+            ..attack.clone()
+        };
+
+        let defender_dice = defense(&attack);
+        let mut defender_rolls = self.rules.dice.defender_rolls(
+            &attacker_rolls,
+            &commanders::dice_sides(&defending.commanders.borrow(), defender_dice),
+        );
+        let bonus = modifiers::defense_bonus(&defending.modifiers.borrow());
+        modifiers::apply_defense_bonus(&mut defender_rolls, bonus);
+
+        if verbose {
+            self.board
+                .set_extra_info(format!("Attacker rolls {}", join(&attacker_rolls, ", ")));
+            self.board.set_extra_info(format!(
+                "{} defends with {} armies{}",
+                defending.name,
+                defender_dice,
+                if attack.roll.is_some() {
+                    " after seeing the roll"
+                } else {
+                    ""
+                }
+            ));
+            self.board.set_extra_info(format!(
+                "Defender rolls {}{}",
+                join(&defender_rolls, ", "),
+                match bonus {
+                    0 => String::new(),
+                    bonus => format!(" ({bonus:+} on the highest die)"),
+                }
+            ));
+        }
+
+        let (attacker_losses, defender_losses) =
+            dice::compare_rolls(&attacker_rolls, &defender_rolls);
+
+        // Remove killed armies
+        *attacking.armies.borrow_mut() -= attacker_losses;
+        *defending.armies.borrow_mut() -= defender_losses;
+
+        // Add killed armies back into the box
+        self.armies_in_box.borrow_mut()[aggressor.get_state().index()] += attacker_losses;
+        // Neutral armies do not belong to any box
+        if let Some(defender) = defender {
+            self.armies_in_box.borrow_mut()[defender.get_state().index()] += defender_losses;
+        }
+
+        (attacker_losses, defender_losses)
+    }
+
+    /// Keeps rolling the dice for the given attack until the territory is captured,
+    /// the attacker can no longer attack or the stop condition of the blitz is met.
+    /// Returns the aggregated losses of the attacker and the defender,
+    /// and the amount of dice the attacker rolled last.
+    fn blitz(&self, attack: &Attack, blitz: Blitz, policy: DefensePolicy) -> (u32, u32, u32) {
+        let attacking = &self.board.territories[attack.attacker];
+        let defending = &self.board.territories[attack.defender];
+
+        let mut attacker_losses = 0;
+        let mut defender_losses = 0;
+        let mut dice = attack.dice;
+        let mut rolls = 0;
+        loop {
+            let attackers = *attacking.armies.borrow();
+            let defenders = *defending.armies.borrow();
+
+            // At least one army must always stay behind
+            if defenders == 0 || attackers <= 1 {
+                break;
+            }
+
+            let stop = match blitz {
+                Blitz::UntilCaptured => false,
+                Blitz::UntilArmiesLeft(armies) => attackers <= armies,
+                Blitz::UntilOdds(odds) => {
+                    dice::capture_probability(attackers - 1, defenders) < odds
+                }
+            };
+            if stop && rolls > 0 {
+                break;
+            }
+
+            dice = min(attack.dice, attackers - 1);
+            let attack = Attack {
+                dice,
+                ..attack.clone()
+            };
+            let (attacker_lost, defender_lost) =
+                self.battle(&attack, false, |attack| match policy {
+                    DefensePolicy::MaxDice => min(2, defenders),
+                    DefensePolicy::Dice(dice) => min(dice, defenders),
+                    DefensePolicy::PerRoll => {
+                        let defender = defending.get_player().unwrap();
+                        self.decide(
+                            &defender,
+                            Decision::Defense,
+                            |game| {
+                                let dice = defender.defend(game, attack);
+                                self.validate_defense(&defender, attack, dice);
+                                dice
+                            },
+                            || min(2, *defending.armies.borrow()),
+                        )
+                    }
+                });
+            attacker_losses += attacker_lost;
+            defender_losses += defender_lost;
+            rolls += 1;
+        }
+
+        (attacker_losses, defender_losses, dice)
+    }
+
+    /// The order in which the players take their turns in the next round.
+    /// The first player is decided the first time this is called.
+    fn round_order(&mut self, verbose: bool) -> Vec<usize> {
+        let seats = self.players.len();
+        let first = *self
+            .first_player
+            .get_or_insert_with(|| match self.rules.turn_order {
+                TurnOrder::DiceRoll => first_player(&self.players.iter().collect(), verbose),
+                _ => 0,
+            });
+
+        let mut order: Vec<usize> = (0..seats)
+            .map(|seat| (first + seat) % seats)
+            .filter(|index| !*self.seats[*index].defeated.borrow())
+            .collect();
+        match self.rules.turn_order {
+            TurnOrder::Fixed | TurnOrder::DiceRoll => {}
+            TurnOrder::Shuffle => order.shuffle(&mut rand::thread_rng()),
+            TurnOrder::WeakestFirst => order.sort_by_key(|index| {
+                let territories = self.seats[*index].get_territories().borrow();
+                let armies: u32 = territories
+                    .iter()
+                    .map(|territory| *territory.armies.borrow())
+                    .sum();
+                (territories.len(), armies, *index)
+            }),
+        }
+        order
+    }
+
+    /// Returns the index of the first player that has fulfilled its secret mission
+    fn fulfilled_mission(&self) -> Option<usize> {
+        (0..self.seats.len())
+            .filter(|index| !*self.seats[*index].defeated.borrow())
+            .find(|index| match &*self.seats[*index].get_mission().borrow() {
+                Some(mission) => mission.is_fulfilled(*index, &self.seats),
+                None => false,
+            })
+    }
+
+    /// Lets the player move armies between two of its adjacent territories once
+    fn free_move(&self, player: &Rc<dyn Player>, verbose: bool) {
+        let fortification = self.decide(
+            player,
+            Decision::Fortify,
+            |game| {
+                let fortification = player.fortify(game);
+                if let Some((from, to, armies)) = fortification {
+                    self.validate_fortification(player, from, to, armies);
+                }
+                fortification
+            },
+            || None,
+        );
+        let Some((from, to, armies)) = fortification else {
+            return;
+        };
+
+        *self.board.territories[from].armies.borrow_mut() -= armies;
+        *self.board.territories[to].armies.borrow_mut() += armies;
+        self.history.borrow_mut().push(Event::Fortify {
+            player: player.get_state().index(),
+            from,
+            to,
+            armies,
+        });
+        if verbose {
+            self.board.set_extra_info(format!(
+                "{} moves {} armies from {} to {}.",
+                player.get_state().name,
+                armies,
+                self.board.territories[from].name,
+                self.board.territories[to].name
+            ));
+            self.board.print_board();
+            self.board.clear_extra_info();
+        }
     }
 }
 
@@ -1,9 +1,11 @@
 use colored::Color::{Black, Blue, Green, Red, White};
+use rist::boards::renderer::ListRenderer;
 use rist::boards::BoardStruct;
 use rist::boards::BoardType::ClassicBoard;
 use rist::players::random_player::RandomPlayer;
 use rist::players::Player;
 use rist::Game;
+use std::env;
 use std::rc::Rc;
 
 fn main() {
@@ -15,6 +17,10 @@ fn main() {
     ];
 
     let board = BoardStruct::new(ClassicBoard, players.len(), None);
+    // `--list` shows the territories as a plain list instead of the map
+    if env::args().any(|arg| arg == "--list") {
+        board.set_renderer(Box::new(ListRenderer));
+    }
     let mut game = Game::new(players, board);
     game.setup(true);
 
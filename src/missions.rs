@@ -0,0 +1,193 @@
+//! Secret missions for the "Secret Mission" variant of the game.
+//! Every player is dealt a hidden mission during setup and the first player to fulfill it wins.
+//!
+//! Missions are generated from the continents and territories of a board,
+//! so every board, including custom ones, gets a suitable deck of missions.
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+use itertools::join;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use serde::{Serialize, Serializer};
+
+use crate::boards::BoardStruct;
+use crate::continent::Continent;
+use crate::players::{Player, Seat};
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub enum Mission {
+    /// Conquer all the given continents
+    /// and `additional` other continents of choice
+    ConquerContinents {
+        #[serde(serialize_with = "continent_indexes")]
+        continents: Vec<Rc<Continent>>,
+        additional: u32,
+    },
+    /// Occupy at least `territories` territories with at least `armies` armies on each of them
+    HoldTerritories { territories: u32, armies: u32 },
+    /// Eliminate the player with the given index.
+    /// Should the target be eliminated by someone else,
+    /// the mission falls back to occupying `fallback` territories.
+    EliminatePlayer {
+        target: usize,
+        name: String,
+        fallback: u32,
+    },
+    /// Occupy at least the given amount of territories
+    OccupyTerritories(u32),
+}
+
+impl Mission {
+    /// Checks whether the player with the given index has fulfilled the mission
+    pub fn is_fulfilled(&self, player: usize, seats: &[Seat]) -> bool {
+        let seat = &seats[player];
+        match self {
+            Mission::ConquerContinents {
+                continents,
+                additional,
+            } => {
+                let owned = seat.get_continents().borrow();
+                continents.iter().all(|continent| owned.contains(continent))
+                    && owned.len() >= continents.len() + *additional as usize
+            }
+            Mission::HoldTerritories {
+                territories,
+                armies,
+            } => {
+                seat.get_territories()
+                    .borrow()
+                    .iter()
+                    .filter(|territory| *territory.armies.borrow() >= *armies)
+                    .count()
+                    >= *territories as usize
+            }
+            Mission::EliminatePlayer {
+                target, fallback, ..
+            } => {
+                let target = &seats[*target];
+                if !target.get_territories().borrow().is_empty() {
+                    return false;
+                }
+                match *target.defeated_by.borrow() {
+                    Some(by) if by == player => true,
+                    _ => Mission::OccupyTerritories(*fallback).is_fulfilled(player, seats),
+                }
+            }
+            Mission::OccupyTerritories(territories) => {
+                seat.get_territories().borrow().len() >= *territories as usize
+            }
+        }
+    }
+}
+
+/// Continents are written as their index on the board
+fn continent_indexes<S: Serializer>(
+    continents: &[Rc<Continent>],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(continents.iter().map(|continent| *continent.index.borrow()))
+}
+
+impl Display for Mission {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mission::ConquerContinents {
+                continents,
+                additional,
+            } => {
+                write!(
+                    f,
+                    "Conquer {}",
+                    join(continents.iter().map(|continent| &continent.name), " and ")
+                )?;
+                if *additional > 0 {
+                    write!(f, " and {additional} other continent(s) of your choice")?;
+                }
+                Ok(())
+            }
+            Mission::HoldTerritories {
+                territories,
+                armies,
+            } => write!(
+                f,
+                "Occupy {territories} territories with at least {armies} armies on each of them"
+            ),
+            Mission::EliminatePlayer { name, fallback, .. } => write!(
+                f,
+                "Eliminate {name}. If someone else eliminates {name}, occupy {fallback} territories"
+            ),
+            Mission::OccupyTerritories(territories) => {
+                write!(f, "Occupy {territories} territories")
+            }
+        }
+    }
+}
+
+/// Generates the deck of missions for the given board and players.
+/// The amount of territories to occupy scales with the size of the board,
+/// using the classic board of 42 territories as reference.
+/// Every pair of continents that together make up at most half of the board is a mission.
+/// Pairs covering at most a quarter of the board require one additional continent of choice.
+/// Continents with impassable territories can never be conquered and are left out.
+pub fn generate_missions(board: &BoardStruct, players: &[Rc<dyn Player>]) -> Vec<Mission> {
+    let size = board.territories.len() as u32;
+    let conquerable = |continent: &Rc<Continent>| {
+        !board
+            .territories
+            .iter()
+            .any(|territory| territory.is_impassable() && territory.continent == *continent)
+    };
+    let occupy = (size * 24).div_ceil(42);
+    let hold = (size * 18).div_ceil(42);
+
+    let mut missions = vec![];
+    for (i, first) in board.continents.iter().enumerate() {
+        for second in &board.continents[i + 1..] {
+            let combined = first.size + second.size;
+            if combined * 2 <= size && conquerable(first) && conquerable(second) {
+                missions.push(Mission::ConquerContinents {
+                    continents: vec![Rc::clone(first), Rc::clone(second)],
+                    additional: if combined * 4 <= size { 1 } else { 0 },
+                });
+            }
+        }
+    }
+
+    missions.push(Mission::HoldTerritories {
+        territories: hold,
+        armies: 2,
+    });
+    missions.push(Mission::OccupyTerritories(occupy));
+
+    for player in players {
+        missions.push(Mission::EliminatePlayer {
+            target: player.get_state().index(),
+            name: String::from(&player.get_state().name),
+            fallback: occupy,
+        });
+    }
+
+    missions
+}
+
+/// Shuffles the deck of missions and deals one mission to the seat of every player.
+/// A player that would be dealt the mission to eliminate itself
+/// receives the fallback mission instead.
+pub fn deal_missions(mut missions: Vec<Mission>, seats: &[Seat]) {
+    assert!(
+        missions.len() >= seats.len(),
+        "Not enough missions for every player"
+    );
+    missions.shuffle(&mut thread_rng());
+
+    for (index, seat) in seats.iter().enumerate() {
+        let mission = match missions.pop().unwrap() {
+            Mission::EliminatePlayer {
+                target, fallback, ..
+            } if target == index => Mission::OccupyTerritories(fallback),
+            mission => mission,
+        };
+        seat.set_mission(Some(mission));
+    }
+}
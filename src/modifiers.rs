@@ -0,0 +1,76 @@
+//! Modifiers change how a single territory behaves, as in Risk Legacy.
+//! They are defined by the board or earned during play.
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum Modifier {
+    /// Adds 1 to the highest die of the defender
+    Fortress,
+    /// Adds the given amount of armies to the reinforcements of the owner
+    City(u32),
+    /// Subtracts 1 from the highest die of the defender
+    Scar,
+}
+
+impl Modifier {
+    /// The amount added to the highest die of the defender of the territory
+    pub fn defense_bonus(&self) -> i32 {
+        match self {
+            Modifier::Fortress => 1,
+            Modifier::City(_) => 0,
+            Modifier::Scar => -1,
+        }
+    }
+
+    /// The armies the owner of the territory receives at the start of its turn
+    pub fn reinforcements(&self) -> u32 {
+        match self {
+            Modifier::City(armies) => *armies,
+            _ => 0,
+        }
+    }
+}
+
+/// The total defense bonus of the given modifiers
+pub fn defense_bonus(modifiers: &[Modifier]) -> i32 {
+    modifiers.iter().map(Modifier::defense_bonus).sum()
+}
+
+/// Applies the defense bonus to the highest roll of the defender.
+/// A roll never drops below 1.
+pub fn apply_defense_bonus(defender_rolls: &mut [u32], bonus: i32) {
+    if let Some(highest) = defender_rolls.iter_mut().max() {
+        *highest = (*highest as i32 + bonus).max(1) as u32;
+    }
+}
+
+impl Display for Modifier {
+    /// The same format is parsed by `FromStr`, so modifiers can be written to files
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Modifier::Fortress => write!(f, "fortress"),
+            Modifier::City(armies) => write!(f, "city {armies}"),
+            Modifier::Scar => write!(f, "scar"),
+        }
+    }
+}
+
+impl FromStr for Modifier {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        match words.as_slice() {
+            ["fortress"] => Ok(Modifier::Fortress),
+            ["city", armies] => armies
+                .parse()
+                .map(Modifier::City)
+                .map_err(|_| format!("Invalid amount of armies for a city: {armies}")),
+            ["scar"] => Ok(Modifier::Scar),
+            _ => Err(format!("Unknown modifier: {s}")),
+        }
+    }
+}
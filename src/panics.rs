@@ -0,0 +1,92 @@
+//! Isolates panics of players, so a single faulty player does not abort a batch of games.
+//! Every panic is reported with the decision the player was making and the state of the board,
+//! after which the game continues as the rules decide.
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+
+/// The decisions the engine asks players to make
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Claim,
+    Placement,
+    Commanders,
+    Attack,
+    Capture,
+    Defense,
+    BlitzDefense,
+    Fortify,
+    Surrender,
+    GameStart,
+    GameOver,
+}
+
+/// What happens when a player panics during a decision
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PanicFallback {
+    /// Continue with the most passive legal decision, e.g. not attacking
+    Default,
+    /// Continue with the default decision and let the player forfeit at the end of the phase
+    Forfeit,
+    /// Abort the game by continuing the panic
+    #[default]
+    Abort,
+}
+
+/// A panic of a player, as caught by the engine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicReport {
+    pub player: usize,
+    pub turn: u64,
+    pub decision: Decision,
+    /// The message the player panicked with
+    pub message: String,
+    /// The owner and armies of every territory when the player panicked
+    pub board: Vec<(Option<usize>, u32)>,
+    pub fallback: PanicFallback,
+}
+
+/// The panic payload of a player that ran out of `Rules::decision_time`,
+/// which makes the player forfeit whatever `Rules::on_panic` says
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecisionTimeout(pub String);
+
+/// The message of a caught panic, if it has one
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(DecisionTimeout(message)) = payload.downcast_ref::<DecisionTimeout>() {
+        String::from(message)
+    } else if let Some(message) = payload.downcast_ref::<&str>() {
+        String::from(*message)
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        String::from(message)
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+impl Display for Decision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Decision::Claim => write!(f, "claiming a territory"),
+            Decision::Placement => write!(f, "placing armies"),
+            Decision::Commanders => write!(f, "buying commanders"),
+            Decision::Attack => write!(f, "attacking"),
+            Decision::Capture => write!(f, "capturing a territory"),
+            Decision::Defense => write!(f, "defending"),
+            Decision::BlitzDefense => write!(f, "defending a blitz"),
+            Decision::Fortify => write!(f, "fortifying"),
+            Decision::Surrender => write!(f, "deciding to surrender"),
+            Decision::GameStart => write!(f, "starting the game"),
+            Decision::GameOver => write!(f, "ending the game"),
+        }
+    }
+}
+
+impl Display for PanicReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Player {} panicked while {} in turn {}: {}",
+            self.player, self.decision, self.turn, self.message
+        )
+    }
+}
@@ -0,0 +1,360 @@
+//! A player that runs as a separate process, so bots can be written in any language.
+//!
+//! The engine writes a single line of JSON to the standard input of the bot for every
+//! decision, and expects a single line of JSON with the decision on its standard output.
+//! Every message has the same shape:
+//! ```json
+//! {"type": "claim", "game": {...}, "events": [...]}
+//! ```
+//! - `game` is the view of the bot on the game, see `GameView`, without the history
+//! - `events` are the events of the history that happened since the previous message
+//! - `attack` is included in `capture`, `defend` and `defend_blitz` messages, see `Attack`
+//! - `result` is included in the `game_over` message, see `GameResult`
+//!
+//! The bot replies to every message with:
+//!
+//! | type           | reply                                                                  |
+//! |----------------|------------------------------------------------------------------------|
+//! | `game_start`   | nothing                                                                |
+//! | `claim`        | the index in `free_territories`, e.g. `3`                              |
+//! | `place`        | pairs of territories and armies, e.g. `[[12, 3], [14, 1]]`             |
+//! | `commanders`   | pairs of commanders and territories, e.g. `[["Land", 12]]`             |
+//! | `attack`       | `null` to stop, or `{"dice": 3, "attacker": 12, "defender": 13}`       |
+//! | `capture`      | the armies moved into the captured territory, e.g. `3`                 |
+//! | `defend`       | the amount of dice, e.g. `2`                                           |
+//! | `defend_blitz` | `"MaxDice"`, `{"Dice": 1}` or `"PerRoll"`                              |
+//! | `fortify`      | `null` to not move, or the territories and armies, e.g. `[12, 14, 3]`  |
+//! | `surrender`    | `true` to forfeit the game                                             |
+//! | `game_over`    | nothing                                                                |
+//!
+//! Enums are written as their variant name, variants with fields as `{"Variant": fields}`.
+//!
+//! A bot that does not reply within the timeout, replies with invalid JSON or a decision
+//! it is not allowed to make, or exits, panics the player,
+//! which the engine handles according to `Rules::on_panic`.
+//! The timeout is shortened to `Rules::decision_time` when the game has a shorter one,
+//! so unlike players in the same process a bot that takes too long is not waited for,
+//! and forfeits with `ForfeitReason::DecisionTime` instead.
+//! A bot that replies after its timeout is not mistaken to reply to the next message:
+//! replies are matched to messages in order, and late replies are discarded.
+//!
+//! Bots are launched as a process, or reached through any other `Connection`,
+//! like the seats of a `Server`.
+use std::cell::{Cell, RefCell};
+use std::io::{self, BufRead, BufReader, Write};
+use std::panic;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use colored::Color;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::commanders::Commander;
+use crate::history::Event;
+use crate::panics::DecisionTimeout;
+use crate::players::{Player, PlayerState, PlayerType};
+use crate::victory::GameResult;
+use crate::view::GameView;
+use crate::{Attack, DefensePolicy};
+
+/// Why no line was received from a connection
+#[derive(Debug)]
+pub enum ReceiveError {
+    /// No line arrived in time
+    Timeout,
+    /// The other side has gone away for good
+    Closed,
+    /// The other side connected again and has missed the last message
+    Reconnected,
+}
+
+/// A connection that exchanges single lines with a bot
+pub trait Connection {
+    /// Sends the single line built by `line`. Replies to earlier lines that have not been
+    /// received are discarded.
+    /// `line` is told whether the other side connected again since the previous line was sent,
+    /// which cannot happen between building and sending the line.
+    fn send(&self, line: &dyn Fn(bool) -> String) -> io::Result<()>;
+
+    /// Waits for a single line for at most the given duration
+    fn receive(&self, timeout: Duration) -> Result<String, ReceiveError>;
+}
+
+/// The standard input and output of a bot process.
+/// The bot replies with a single line to every line it is waited for, in order,
+/// so lines are matched to the lines they reply to by counting them.
+struct ProcessConnection {
+    process: Child,
+    /// Closed when the player is dropped
+    stdin: RefCell<Option<ChildStdin>>,
+    lines: Receiver<String>,
+    /// The amount of lines sent so far
+    sent: Cell<usize>,
+    /// The amount of sent lines that have been waited for, each of which the bot replies to
+    asked: Cell<usize>,
+    /// The last sent line that has been waited for
+    last_asked: Cell<usize>,
+    /// The amount of lines received so far, including late replies that were discarded
+    received: Cell<usize>,
+}
+
+impl ProcessConnection {
+    fn new(mut command: Command) -> io::Result<Self> {
+        let mut process = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = process.stdin.take().unwrap();
+        let stdout = process.stdout.take().unwrap();
+
+        // Reading blocks, so the output is read on its own thread to be able to time out
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(ProcessConnection {
+            process,
+            stdin: RefCell::from(Some(stdin)),
+            lines,
+            sent: Cell::new(0),
+            asked: Cell::new(0),
+            last_asked: Cell::new(0),
+            received: Cell::new(0),
+        })
+    }
+}
+
+impl Connection for ProcessConnection {
+    fn send(&self, line: &dyn Fn(bool) -> String) -> io::Result<()> {
+        let mut stdin = self.stdin.borrow_mut();
+        let stdin = stdin.as_mut().unwrap();
+        writeln!(stdin, "{}", line(false))?;
+        self.sent.set(self.sent.get() + 1);
+        stdin.flush()
+    }
+
+    fn receive(&self, timeout: Duration) -> Result<String, ReceiveError> {
+        if self.last_asked.get() != self.sent.get() {
+            self.last_asked.set(self.sent.get());
+            self.asked.set(self.asked.get() + 1);
+        }
+        let deadline = Instant::now() + timeout;
+        loop {
+            let line = self
+                .lines
+                .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                .map_err(|error| match error {
+                    RecvTimeoutError::Timeout => ReceiveError::Timeout,
+                    RecvTimeoutError::Disconnected => ReceiveError::Closed,
+                })?;
+            self.received.set(self.received.get() + 1);
+            // Earlier lines are late replies to lines that timed out
+            if self.received.get() >= self.asked.get() {
+                return Ok(line);
+            }
+        }
+    }
+}
+
+impl Drop for ProcessConnection {
+    /// The bot does not outlive the player.
+    /// It gets a moment to exit by itself once its input is closed.
+    fn drop(&mut self) {
+        self.stdin.get_mut().take();
+        let deadline = Instant::now() + Duration::from_secs(1);
+        while Instant::now() < deadline {
+            if let Ok(Some(_)) = self.process.try_wait() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+pub struct ExternalPlayer {
+    state: PlayerState,
+    connection: Box<dyn Connection>,
+    /// How long the bot may take to reply to a single message,
+    /// unless the rules of the game allow less time, see `Rules::decision_time`
+    timeout: Duration,
+    /// The amount of events of the history that have already been sent to the bot
+    events_sent: RefCell<usize>,
+}
+
+/// A single message to the bot
+#[derive(Serialize)]
+struct Message<'a> {
+    #[serde(rename = "type")]
+    kind: &'a str,
+    game: &'a GameView<'a>,
+    events: &'a [Event],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attack: Option<&'a Attack>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<&'a GameResult>,
+}
+
+impl ExternalPlayer {
+    /// Launches the bot with the given command
+    pub fn new(
+        name: &str,
+        background: Color,
+        foreground: Color,
+        command: Command,
+        timeout: Duration,
+    ) -> io::Result<Self> {
+        Ok(ExternalPlayer::with_connection(
+            name,
+            background,
+            foreground,
+            Box::new(ProcessConnection::new(command)?),
+            timeout,
+        ))
+    }
+
+    /// Plays with a bot that is reached through the given connection
+    pub fn with_connection(
+        name: &str,
+        background: Color,
+        foreground: Color,
+        connection: Box<dyn Connection>,
+        timeout: Duration,
+    ) -> Self {
+        ExternalPlayer {
+            state: PlayerState::new(PlayerType::ExternalPlayer, name, background, foreground),
+            connection,
+            timeout,
+            events_sent: RefCell::from(0),
+        }
+    }
+
+    /// Sends a message to the bot, together with the events it has not seen yet
+    fn send(
+        &self,
+        kind: &str,
+        game: &GameView,
+        attack: Option<&Attack>,
+        result: Option<&GameResult>,
+    ) {
+        let line = |reconnected: bool| {
+            // The bot gets the entire history again after it connected again
+            let sent = if reconnected {
+                0
+            } else {
+                *self.events_sent.borrow()
+            };
+            let message = Message {
+                kind,
+                game,
+                events: &game.history[sent.min(game.history.len())..],
+                attack,
+                result,
+            };
+            serde_json::to_string(&message).unwrap()
+        };
+        self.connection
+            .send(&line)
+            .unwrap_or_else(|error| panic!("{} cannot be reached: {error}", self.state.name));
+        *self.events_sent.borrow_mut() = game.history.len();
+    }
+
+    /// Sends a message to the bot and waits for its reply
+    fn ask<T: DeserializeOwned>(&self, kind: &str, game: &GameView, attack: Option<&Attack>) -> T {
+        let timeout = game
+            .decision_time
+            .map_or(self.timeout, |limit| limit.min(self.timeout));
+        let deadline = Instant::now() + timeout;
+        self.send(kind, game, attack, None);
+        let line = loop {
+            match self
+                .connection
+                .receive(deadline.saturating_duration_since(Instant::now()))
+            {
+                Ok(line) => break line,
+                // The bot missed the message
+                Err(ReceiveError::Reconnected) => self.send(kind, game, attack, None),
+                Err(ReceiveError::Timeout) => {
+                    let message = format!(
+                        "{} did not reply to {kind} within {timeout:?}",
+                        self.state.name
+                    );
+                    // Running out of the decision time of the rules is a forfeit, not a fault
+                    if game
+                        .decision_time
+                        .is_some_and(|limit| limit <= self.timeout)
+                    {
+                        panic::panic_any(DecisionTimeout(message))
+                    }
+                    panic!("{message}")
+                }
+                Err(ReceiveError::Closed) => panic!("{} exited during {kind}", self.state.name),
+            }
+        };
+        serde_json::from_str(&line).unwrap_or_else(|error| {
+            panic!(
+                "{} replied to {kind} with invalid JSON: {error}: {line}",
+                self.state.name
+            )
+        })
+    }
+}
+
+impl Player for ExternalPlayer {
+    fn get_state(&self) -> &PlayerState {
+        &self.state
+    }
+
+    fn claim_territory(&self, game: &GameView) -> usize {
+        self.ask("claim", game, None)
+    }
+
+    fn place_armies(&self, game: &GameView) -> Vec<(usize, u32)> {
+        self.ask("place", game, None)
+    }
+
+    fn buy_commanders(&self, game: &GameView) -> Vec<(Commander, usize)> {
+        self.ask("commanders", game, None)
+    }
+
+    fn attack(&self, game: &GameView) -> Option<Attack> {
+        self.ask("attack", game, None)
+    }
+
+    fn capture(&self, game: &GameView, attack: &Attack) -> u32 {
+        self.ask("capture", game, Some(attack))
+    }
+
+    fn defend(&self, game: &GameView, attack: &Attack) -> u32 {
+        self.ask("defend", game, Some(attack))
+    }
+
+    fn defend_blitz(&self, game: &GameView, attack: &Attack) -> DefensePolicy {
+        self.ask("defend_blitz", game, Some(attack))
+    }
+
+    fn fortify(&self, game: &GameView) -> Option<(usize, usize, u32)> {
+        self.ask("fortify", game, None)
+    }
+
+    fn surrender(&self, game: &GameView) -> bool {
+        self.ask("surrender", game, None)
+    }
+
+    fn game_start(&self, game: &GameView) {
+        self.send("game_start", game, None, None);
+    }
+
+    fn game_over(&self, game: &GameView, result: &GameResult) {
+        self.send("game_over", game, None, Some(result));
+    }
+}
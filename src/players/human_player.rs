@@ -0,0 +1,301 @@
+use colored::Color;
+use std::cmp::min;
+use std::io::Stdout;
+use std::rc::Rc;
+
+use ratatui::backend::{Backend, CrosstermBackend};
+
+use crate::commanders::Commander;
+use crate::players::{Player, PlayerState, PlayerType};
+use crate::tui::{Prompt, Screen};
+use crate::view::GameView;
+use crate::{Attack, DefensePolicy};
+
+/// A player that makes its decisions with the keyboard, see `Tui::human`
+pub struct HumanPlayer<B: Backend = CrosstermBackend<Stdout>> {
+    state: PlayerState,
+    screen: Rc<Screen<B>>,
+}
+
+impl<B: Backend> HumanPlayer<B> {
+    pub(crate) fn new(
+        name: &str,
+        background: Color,
+        foreground: Color,
+        screen: Rc<Screen<B>>,
+    ) -> Self {
+        HumanPlayer {
+            state: PlayerState::new(PlayerType::HumanPlayer, name, background, foreground),
+            screen,
+        }
+    }
+
+    /// Picks one of the given territories, `None` when skipped
+    fn territory(
+        &self,
+        game: &GameView,
+        question: &str,
+        territories: &[usize],
+        optional: bool,
+    ) -> Option<usize> {
+        self.screen
+            .ask(game, question, Prompt::Territory(territories, 0), optional)
+            .map(|index| territories[index])
+    }
+
+    /// Picks an amount in the given range, starting at the maximum. `None` when skipped.
+    fn amount(&self, game: &GameView, question: &str, min: u32, max: u32) -> Option<u32> {
+        self.screen
+            .ask(game, question, Prompt::Amount(min, max, max), true)
+            .map(|amount| amount as u32)
+    }
+}
+
+impl<B: Backend> Player for HumanPlayer<B> {
+    fn get_state(&self) -> &PlayerState {
+        &self.state
+    }
+
+    fn claim_territory(&self, game: &GameView) -> usize {
+        let free = &game.board.free_territories;
+        let territory = self
+            .territory(game, "Claim a territory", free, false)
+            .unwrap();
+        free.iter().position(|free| *free == territory).unwrap()
+    }
+
+    /// The armies that are not placed stay in the reserve
+    fn place_armies(&self, game: &GameView) -> Vec<(usize, u32)> {
+        let territories: Vec<usize> = game
+            .territories()
+            .iter()
+            .map(|territory| territory.index)
+            .collect();
+        let mut remaining = game.reserve();
+        let mut placement = vec![];
+        // The placement so far is shown on the map
+        let mut view = game.clone();
+        while remaining > 0 && !territories.is_empty() {
+            let question = format!("Place {remaining} armies");
+            let Some(territory) = self.territory(&view, &question, &territories, true) else {
+                break;
+            };
+            let name = &game.board.territories[territory].name;
+            let question = format!("Place armies on {name}");
+            let Some(armies) = self.amount(&view, &question, 1, remaining) else {
+                continue;
+            };
+            remaining -= armies;
+            placement.push((territory, armies));
+            if let Some(placed) = view.board.territories[territory].armies.as_mut() {
+                *placed += armies;
+            }
+            view.players[game.player()].reserve = remaining;
+        }
+        placement
+    }
+
+    fn buy_commanders(&self, game: &GameView) -> Vec<(Commander, usize)> {
+        let territories: Vec<usize> = game
+            .territories()
+            .iter()
+            .map(|territory| territory.index)
+            .collect();
+        let mut energy = game.players[game.player()].energy;
+        let mut available: Vec<Commander> = Commander::ALL
+            .into_iter()
+            .filter(|commander| {
+                !game.territories().iter().any(|territory| {
+                    territory
+                        .commanders
+                        .as_ref()
+                        .is_some_and(|commanders| commanders.contains(commander))
+                })
+            })
+            .collect();
+
+        let mut purchases = vec![];
+        loop {
+            let affordable: Vec<Commander> = available
+                .iter()
+                .copied()
+                .filter(|commander| commander.cost() <= energy)
+                .collect();
+            if affordable.is_empty() || territories.is_empty() {
+                return purchases;
+            }
+            let options: Vec<String> = affordable
+                .iter()
+                .map(|commander| format!("{commander} ({})", commander.cost()))
+                .collect();
+            let question = format!("Buy a commander with {energy} energy");
+            let Some(choice) = self
+                .screen
+                .ask(game, &question, Prompt::Choice(&options, 0), true)
+            else {
+                return purchases;
+            };
+            let commander = affordable[choice];
+            let question = format!("Place the {commander}");
+            let Some(territory) = self.territory(game, &question, &territories, true) else {
+                continue;
+            };
+            energy -= commander.cost();
+            available.retain(|available| *available != commander);
+            purchases.push((commander, territory));
+        }
+    }
+
+    /// Attacks a single time with the chosen dice, or stops attacking with `esc`
+    fn attack(&self, game: &GameView) -> Option<Attack> {
+        // The territories that can be attacked from every territory
+        let targets = |attacker: usize| -> Vec<usize> {
+            let territory = &game.board.territories[attacker];
+            territory
+                .connections
+                .iter()
+                .zip(&territory.connection_types)
+                .filter(|(adjacent, connection)| {
+                    let adjacent = &game.board.territories[**adjacent];
+                    adjacent.owner != Some(game.player())
+                        && !adjacent.impassable
+                        && connection.allows_attack()
+                })
+                .map(|(adjacent, _)| *adjacent)
+                .collect()
+        };
+        let attackers: Vec<usize> = game
+            .territories()
+            .iter()
+            .filter(|territory| territory.armies.unwrap_or(0) >= 2)
+            .map(|territory| territory.index)
+            .filter(|territory| !targets(*territory).is_empty())
+            .collect();
+        if attackers.is_empty() {
+            return None;
+        }
+
+        loop {
+            let attacker =
+                self.territory(game, "Attack from, or stop attacking", &attackers, true)?;
+            let attacking = &game.board.territories[attacker];
+            let question = format!("Attack from {} on", attacking.name);
+            let Some(defender) = self.territory(game, &question, &targets(attacker), true) else {
+                continue;
+            };
+            let defending = &game.board.territories[defender];
+            let connection = attacking.connection_types[attacking
+                .connections
+                .iter()
+                .position(|adjacent| *adjacent == defender)
+                .unwrap()];
+            let dice = min(
+                connection.max_attack_dice(),
+                attacking.armies.unwrap_or(1) - 1,
+            );
+            let question = format!("Attack {} with dice", defending.name);
+            let Some(dice) = self.amount(game, &question, 1, dice) else {
+                continue;
+            };
+            return Some(Attack {
+                dice,
+                attacker,
+                defender,
+                blitz: None,
+                roll: None,
+            });
+        }
+    }
+
+    fn capture(&self, game: &GameView, attack: &Attack) -> u32 {
+        let armies = game.board.territories[attack.attacker].armies.unwrap();
+        let question = format!(
+            "Move armies into {}",
+            game.board.territories[attack.defender].name
+        );
+        self.screen
+            .ask(
+                game,
+                &question,
+                Prompt::Amount(min(attack.dice, armies - 1), armies - 1, armies - 1),
+                false,
+            )
+            .unwrap() as u32
+    }
+
+    fn defend(&self, game: &GameView, attack: &Attack) -> u32 {
+        let defending = &game.board.territories[attack.defender];
+        let dice = min(2, defending.armies.unwrap());
+        let mut question = format!(
+            "{} attacks {} with {} dice, defend with",
+            game.board.territories[attack.attacker].name, defending.name, attack.dice
+        );
+        if let Some(roll) = &attack.roll {
+            question += &format!(" (rolled {roll:?})");
+        }
+        self.screen
+            .ask(game, &question, Prompt::Amount(1, dice, dice), false)
+            .unwrap() as u32
+    }
+
+    fn fortify(&self, game: &GameView) -> Option<(usize, usize, u32)> {
+        // The territories that armies can be moved to from every territory
+        let targets = |source: usize| -> Vec<usize> {
+            let territory = &game.board.territories[source];
+            territory
+                .connections
+                .iter()
+                .zip(&territory.connection_types)
+                .filter(|(adjacent, connection)| {
+                    game.board.territories[**adjacent].owner == Some(game.player())
+                        && connection.allows_fortify()
+                })
+                .map(|(adjacent, _)| *adjacent)
+                .collect()
+        };
+        let sources: Vec<usize> = game
+            .territories()
+            .iter()
+            .filter(|territory| territory.armies.unwrap_or(0) >= 2)
+            .map(|territory| territory.index)
+            .filter(|territory| !targets(*territory).is_empty())
+            .collect();
+        if sources.is_empty() {
+            return None;
+        }
+
+        loop {
+            let from = self.territory(game, "Fortify from, or end the turn", &sources, true)?;
+            let source = &game.board.territories[from];
+            let question = format!("Move armies from {} to", source.name);
+            let Some(to) = self.territory(game, &question, &targets(from), true) else {
+                continue;
+            };
+            let question = format!("Move armies to {}", game.board.territories[to].name);
+            let Some(armies) = self.amount(game, &question, 1, source.armies.unwrap_or(1) - 1)
+            else {
+                continue;
+            };
+            return Some((from, to, armies));
+        }
+    }
+
+    fn defend_blitz(&self, game: &GameView, attack: &Attack) -> DefensePolicy {
+        let question = format!(
+            "{} blitzes {}",
+            game.board.territories[attack.attacker].name,
+            game.board.territories[attack.defender].name
+        );
+        let options = [
+            String::from("Defend with as many dice as possible"),
+            String::from("Decide on every roll"),
+        ];
+        match self
+            .screen
+            .ask(game, &question, Prompt::Choice(&options, 0), false)
+        {
+            Some(0) => DefensePolicy::MaxDice,
+            _ => DefensePolicy::PerRoll,
+        }
+    }
+}
@@ -1,8 +1,14 @@
 mod player_state;
+mod seat;
 
+pub mod external_player;
+pub mod human_player;
 mod player;
 pub mod random_player;
 
+pub use external_player::*;
+pub use human_player::*;
 pub use player::*;
-use player_state::*;
+pub use player_state::*;
 pub use random_player::*;
+pub use seat::*;
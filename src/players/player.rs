@@ -1,45 +1,80 @@
-use crate::boards::BoardStruct;
+use crate::commanders::Commander;
 use crate::players::PlayerState;
-use crate::territory::Territory;
-use crate::Attack;
+use crate::victory::GameResult;
+use crate::view::GameView;
+use crate::{Attack, DefensePolicy};
 use itertools::enumerate;
 use std::rc::Rc;
 
 /// Implement this trait to create your own player
+/// Every decision receives the view of the player on the game.
+/// Depending on the fog of war rules, parts of the board may be hidden.
+/// Territories are referred to by their index.
 pub trait Player {
     /// Get the internal state of the player
     fn get_state(&self) -> &PlayerState;
 
     /// Allows a player to claim a territory that is not yet claimed
     /// Returns the index of the free territory
-    fn claim_territory(&self, board: &BoardStruct) -> usize;
+    fn claim_territory(&self, game: &GameView) -> usize;
 
     /// Allows the player to place armies on owned territories
     /// Returns a list of tuples containing the territories to place troops on
-    fn place_armies(&self, board: &BoardStruct) -> Vec<(Rc<Territory>, u32)>;
+    fn place_armies(&self, game: &GameView) -> Vec<(usize, u32)>;
+
+    /// When playing with commanders, allows the player to buy commanders with its energy
+    /// Returns a list of tuples containing the commanders and the territories to place them on
+    fn buy_commanders(&self, game: &GameView) -> Vec<(Commander, usize)>;
 
     /// Gives the player an option to attack
     /// To end the attacking phase the player returns `None`
-    fn attack(&self, board: &BoardStruct) -> Option<Attack>;
+    fn attack(&self, game: &GameView) -> Option<Attack>;
 
     /// When a player takes a territory it must assign a number of armies to that territory
     /// no less than the number of dice rolled
-    fn capture(&self, attack: &Attack) -> u32;
+    fn capture(&self, game: &GameView, attack: &Attack) -> u32;
 
     /// Called when the player is being attacked
     /// The player must return with how many dice it wishes to defend its territory
-    fn defend(&self, attack: &Attack) -> u32;
+    /// If the rules allow it, the roll of the attacker is already known
+    fn defend(&self, game: &GameView, attack: &Attack) -> u32;
+
+    /// Called when the player is being attacked by a blitz attack
+    /// The player decides how many dice it rolls for the entire battle
+    fn defend_blitz(&self, game: &GameView, attack: &Attack) -> DefensePolicy;
+
+    /// Called at the end of every turn of the player, after its attacks
+    /// Returns the territory to move armies from, the territory to move them to and the armies,
+    /// over a connection that allows fortifying. At least one army stays behind.
+    /// To not move any armies the player returns `None`
+    fn fortify(&self, _game: &GameView) -> Option<(usize, usize, u32)> {
+        None
+    }
+
+    /// Called at the start of every turn of the player, before its reinforcements
+    /// Returns `true` to forfeit the game, its territories become neutral
+    fn surrender(&self, _game: &GameView) -> bool {
+        false
+    }
+
+    /// Called once before the territories are claimed
+    fn game_start(&self, _game: &GameView) {}
+
+    /// Called once when the game is over, with the ranked result
+    fn game_over(&self, _game: &GameView, _result: &GameResult) {}
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum PlayerType {
     Unimplemented,
     RandomPlayer,
+    ExternalPlayer,
+    HumanPlayer,
 }
 
 /// Generated ids for a list of all players. We use the player index as ID
 pub fn generate_ids(players: &Vec<Rc<dyn Player>>) {
     for (i, player) in enumerate(players) {
-        *player.get_state().index.borrow_mut() = i;
+        player.get_state().set_index(i);
     }
 }
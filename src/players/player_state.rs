@@ -1,67 +1,43 @@
-use crate::continent::Continent;
 use crate::players::PlayerType;
-use crate::territory::Territory;
 use colored::{Color, ColoredString, Colorize};
-use std::cell::RefCell;
+use std::cell::Cell;
 use std::fmt::{Display, Formatter};
-use std::rc::Rc;
 
-/// The default internal state of a player
-/// `Player` gets used with an `Rc` and can therefore only have mutable fields with a `RefCell`
+/// The identity of a player, owned by the player itself.
+/// Everything the player holds during a game is owned by the engine, see `Seat`.
 #[derive(Debug, PartialEq, Eq)]
 pub struct PlayerState {
     pub player_type: PlayerType,
-    pub index: RefCell<usize>,
+    /// Assigned by the engine, see `generate_ids`
+    index: Cell<usize>,
     pub name: String,
-    pub armies: RefCell<u32>,
-    pub(crate) territories: RefCell<Vec<Rc<Territory>>>,
-    continents: RefCell<Vec<Rc<Continent>>>,
     foreground: Color,
     background: Color,
-    pub defeated: RefCell<bool>,
 }
 
 impl PlayerState {
     pub fn new(player: PlayerType, name: &str, background: Color, foreground: Color) -> Self {
         PlayerState {
             player_type: player,
-            index: RefCell::from(0),
+            index: Cell::from(0),
             name: String::from(name),
-            armies: RefCell::from(0),
-            territories: RefCell::from(vec![]),
-            continents: RefCell::from(vec![]),
             foreground,
             background,
-            defeated: RefCell::from(false),
         }
     }
 
-    pub fn get_territories(&self) -> &RefCell<Vec<Rc<Territory>>> {
-        &self.territories
+    /// The index of the player in the game
+    pub fn index(&self) -> usize {
+        self.index.get()
     }
 
-    pub fn add_territory(&self, territory: Rc<Territory>) {
-        self.territories.borrow_mut().push(territory);
+    pub(crate) fn set_index(&self, index: usize) {
+        self.index.set(index);
     }
 
-    pub fn remove_territory(&self, item: &Rc<Territory>) {
-        self.territories
-            .borrow_mut()
-            .retain(|territory| *territory != *item);
-    }
-
-    pub fn get_continents(&self) -> &RefCell<Vec<Rc<Continent>>> {
-        &self.continents
-    }
-
-    pub fn add_continent(&self, continent: Rc<Continent>) {
-        self.continents.borrow_mut().push(continent);
-    }
-
-    pub fn remove_continent(&self, item: &Rc<Continent>) {
-        self.continents
-            .borrow_mut()
-            .retain(|continent| *continent != *item);
+    /// The background and foreground colors of the player
+    pub fn colors(&self) -> (Color, Color) {
+        (self.background, self.foreground)
     }
 
     /// Color the text to the color of the player
@@ -76,26 +52,10 @@ impl Display for PlayerState {
             f,
             "{}\n\
             \tplayer: {:?}\n\
-            \tindex: {}\n\
-            \tarmies: {}\n\
-            \tterritories: {}\n\
-            \tcontinents: {}",
+            \tindex: {}",
             self.name,
             self.player_type,
-            self.index.borrow(),
-            self.armies.borrow(),
-            self.territories
-                .borrow()
-                .iter()
-                .map(|territory| &territory.name[..])
-                .collect::<Vec<&str>>()
-                .join(", "),
-            self.continents
-                .borrow()
-                .iter()
-                .map(|continent| &continent.name[..])
-                .collect::<Vec<&str>>()
-                .join(", "),
+            self.index(),
         )
     }
 }
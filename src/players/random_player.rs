@@ -1,14 +1,14 @@
 use colored::Color;
 use std::cmp::min;
-use std::rc::Rc;
 
 use rand::distributions::Uniform;
+use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng};
 
-use crate::boards::BoardStruct;
+use crate::commanders::Commander;
 use crate::players::{Player, PlayerState, PlayerType};
-use crate::territory::Territory;
-use crate::Attack;
+use crate::view::GameView;
+use crate::{Attack, Blitz, DefensePolicy};
 
 pub struct RandomPlayer {
     state: PlayerState,
@@ -27,35 +27,64 @@ impl Player for RandomPlayer {
         &self.state
     }
 
-    fn claim_territory(&self, board: &BoardStruct) -> usize {
-        let distribution = Uniform::new_inclusive(0, &board.free_territories.len() - 1);
+    fn claim_territory(&self, game: &GameView) -> usize {
+        let distribution = Uniform::new_inclusive(0, &game.board.free_territories.len() - 1);
         let mut rng = thread_rng();
         rng.sample(distribution)
     }
 
-    fn place_armies(&self, board: &BoardStruct) -> Vec<(Rc<Territory>, u32)> {
+    fn place_armies(&self, game: &GameView) -> Vec<(usize, u32)> {
         let mut rng = thread_rng();
-        let uniform_territories = Uniform::new(0, &self.state.territories.borrow().len() - 1);
+        let territories = game.territories();
+        if territories.is_empty() {
+            return vec![];
+        }
+        let uniform_territories = Uniform::new(0, territories.len());
         let mut armies_placed = 0;
         let mut placement = vec![];
         for _ in 0..rng.sample(uniform_territories) {
-            let territory = &self.state.territories.borrow()[rng.sample(uniform_territories)];
-            let uniform_armies = Uniform::new(0, *self.state.armies.borrow() - armies_placed);
-            let armies = rng.sample(uniform_armies);
+            let territory = territories[rng.sample(uniform_territories)];
+            let armies = rng.gen_range(0..=game.reserve() - armies_placed);
             armies_placed += armies;
 
             if armies > 0 {
-                placement.push((
-                    Rc::clone(&board.territories[*territory.index.borrow()]),
-                    armies,
-                ));
+                placement.push((territory.index, armies));
             }
         }
         placement
     }
 
+    /// The random player buys random commanders it does not yet have with all of its energy
+    fn buy_commanders(&self, game: &GameView) -> Vec<(Commander, usize)> {
+        let mut rng = thread_rng();
+        let territories = game.territories();
+        let mut energy = game.players[game.player()].energy;
+        let mut available: Vec<Commander> = Commander::ALL
+            .into_iter()
+            .filter(|commander| {
+                !territories.iter().any(|territory| {
+                    territory
+                        .commanders
+                        .as_ref()
+                        .is_some_and(|commanders| commanders.contains(commander))
+                })
+            })
+            .collect();
+        available.shuffle(&mut rng);
+
+        let mut purchases = vec![];
+        for commander in available {
+            if energy >= commander.cost() {
+                energy -= commander.cost();
+                let territory = territories[rng.gen_range(0..territories.len())];
+                purchases.push((commander, territory.index));
+            }
+        }
+        purchases
+    }
+
     /// The random player attacks half of the times
-    fn attack(&self, _board: &BoardStruct) -> Option<Attack> {
+    fn attack(&self, game: &GameView) -> Option<Attack> {
         let mut rng = thread_rng();
         if rng.gen::<f32>() < 0.5 {
             return None;
@@ -63,24 +92,28 @@ impl Player for RandomPlayer {
 
         // Create all valid attacks
         let mut attacks = vec![];
-        for territory in &*self.state.territories.borrow() {
+        for territory in game.territories() {
+            let armies = territory.armies.unwrap();
             // An attacker should have at least 2 armies
-            if *territory.armies.borrow() >= 2 {
-                for adjacent_territory in &*territory.connections.borrow() {
+            if armies >= 2 {
+                for (adjacent_territory, connection) in territory
+                    .connections
+                    .iter()
+                    .zip(&territory.connection_types)
+                {
                     // Can only attack from a territory adjacent to an enemy territory
-                    if *adjacent_territory
-                        .upgrade()
-                        .unwrap()
-                        .get_player()
-                        .unwrap()
-                        .get_state()
-                        != self.state
+                    let adjacent = &game.board.territories[*adjacent_territory];
+                    if adjacent.owner != Some(game.player())
+                        && !adjacent.impassable
+                        && connection.allows_attack()
                     {
-                        let dice = Uniform::new(1, min(4, *territory.armies.borrow()));
+                        let dice = Uniform::new(1, min(connection.max_attack_dice() + 1, armies));
                         attacks.push(Attack {
                             dice: rng.sample(dice),
-                            attacker: Rc::clone(territory),
-                            defender: Rc::clone(&adjacent_territory.upgrade().unwrap()),
+                            attacker: territory.index,
+                            defender: *adjacent_territory,
+                            blitz: random_blitz(),
+                            roll: None,
                         })
                     }
                 }
@@ -90,27 +123,20 @@ impl Player for RandomPlayer {
         // Pick a random attack from the valid attacks
         if !attacks.is_empty() {
             let dist = Uniform::new(0, attacks.len());
-            let attack = &attacks[rng.sample(dist)];
-            return Some(Attack {
-                dice: attack.dice,
-                attacker: Rc::clone(&attack.attacker),
-                defender: Rc::clone(&attack.defender),
-            });
+            return Some(attacks.swap_remove(rng.sample(dist)));
         }
         None
     }
 
-    fn capture(&self, attack: &Attack) -> u32 {
+    fn capture(&self, game: &GameView, attack: &Attack) -> u32 {
+        let armies = game.board.territories[attack.attacker].armies.unwrap();
         let mut rng = thread_rng();
-        let uniform = Uniform::new(
-            min(attack.dice, *attack.attacker.armies.borrow()),
-            *attack.attacker.armies.borrow(),
-        );
+        let uniform = Uniform::new(min(attack.dice, armies), armies);
         rng.sample(uniform)
     }
 
-    fn defend(&self, attack: &Attack) -> u32 {
-        if *attack.defender.armies.borrow() == 1 {
+    fn defend(&self, game: &GameView, attack: &Attack) -> u32 {
+        if game.board.territories[attack.defender].armies.unwrap() == 1 {
             return 1;
         }
 
@@ -118,4 +144,24 @@ impl Player for RandomPlayer {
         let uniform = Uniform::new(1, 3);
         rng.sample(uniform)
     }
+
+    fn defend_blitz(&self, _game: &GameView, _attack: &Attack) -> DefensePolicy {
+        let mut rng = thread_rng();
+        match rng.gen_range(0..3) {
+            0 => DefensePolicy::MaxDice,
+            1 => DefensePolicy::Dice(1),
+            _ => DefensePolicy::PerRoll,
+        }
+    }
+}
+
+/// Picks a random blitz, or no blitz at all
+fn random_blitz() -> Option<Blitz> {
+    let mut rng = thread_rng();
+    match rng.gen_range(0..4) {
+        0 => Some(Blitz::UntilCaptured),
+        1 => Some(Blitz::UntilArmiesLeft(rng.gen_range(1..5))),
+        2 => Some(Blitz::UntilOdds(rng.gen())),
+        _ => None,
+    }
 }
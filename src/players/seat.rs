@@ -0,0 +1,99 @@
+use crate::continent::Continent;
+use crate::missions::Mission;
+use crate::territory::Territory;
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+/// Everything a player holds during a game, by player index.
+/// Owned by the engine, players only ever see it through a `GameView`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Seat {
+    /// The armies available to place on the board
+    pub armies: RefCell<u32>,
+    /// Energy to buy commanders with
+    pub energy: RefCell<u32>,
+    territories: RefCell<Vec<Rc<Territory>>>,
+    continents: RefCell<Vec<Rc<Continent>>>,
+    pub defeated: RefCell<bool>,
+    /// The index of the player that defeated this player
+    pub defeated_by: RefCell<Option<usize>>,
+    /// The secret mission of the player, if playing with secret missions
+    mission: RefCell<Option<Mission>>,
+    /// The capital of the player, if playing for capitals
+    capital: RefCell<Option<Rc<Territory>>>,
+}
+
+impl Seat {
+    pub fn new() -> Self {
+        Seat::default()
+    }
+
+    pub fn get_territories(&self) -> &RefCell<Vec<Rc<Territory>>> {
+        &self.territories
+    }
+
+    pub fn add_territory(&self, territory: Rc<Territory>) {
+        self.territories.borrow_mut().push(territory);
+    }
+
+    pub fn remove_territory(&self, item: &Rc<Territory>) {
+        self.territories
+            .borrow_mut()
+            .retain(|territory| *territory != *item);
+    }
+
+    pub fn get_continents(&self) -> &RefCell<Vec<Rc<Continent>>> {
+        &self.continents
+    }
+
+    pub fn add_continent(&self, continent: Rc<Continent>) {
+        self.continents.borrow_mut().push(continent);
+    }
+
+    pub fn remove_continent(&self, item: &Rc<Continent>) {
+        self.continents
+            .borrow_mut()
+            .retain(|continent| *continent != *item);
+    }
+
+    pub fn get_mission(&self) -> &RefCell<Option<Mission>> {
+        &self.mission
+    }
+
+    pub fn set_mission(&self, mission: Option<Mission>) {
+        *self.mission.borrow_mut() = mission;
+    }
+
+    pub fn get_capital(&self) -> &RefCell<Option<Rc<Territory>>> {
+        &self.capital
+    }
+
+    pub fn set_capital(&self, capital: Option<Rc<Territory>>) {
+        *self.capital.borrow_mut() = capital;
+    }
+}
+
+impl Display for Seat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "\tarmies: {}\n\
+            \tterritories: {}\n\
+            \tcontinents: {}",
+            self.armies.borrow(),
+            self.territories
+                .borrow()
+                .iter()
+                .map(|territory| &territory.name[..])
+                .collect::<Vec<&str>>()
+                .join(", "),
+            self.continents
+                .borrow()
+                .iter()
+                .map(|continent| &continent.name[..])
+                .collect::<Vec<&str>>()
+                .join(", "),
+        )
+    }
+}
@@ -0,0 +1,246 @@
+//! Policies that decide how many armies a player receives at the start of its turn.
+//! The classic policy rewards a third of the occupied territories, with a minimum of 3,
+//! plus the armies reward of every continent occupied entirely.
+//! Policies can be combined to balance boards that are not classic.
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+use crate::history::Event;
+use crate::view::{ContinentView, GameView};
+
+/// A part of the reinforcements with the reason it is rewarded, e.g. "occupying 12 territories"
+pub type Reward = (String, u32);
+
+pub trait ReinforcementPolicy: Debug {
+    /// The armies the player of the given view receives at the start of its turn
+    fn reinforcements(&self, game: &GameView) -> Vec<Reward>;
+}
+
+/// The sum of the rewards of multiple policies
+#[derive(Debug, Clone)]
+pub struct Reinforcements(pub Vec<Rc<dyn ReinforcementPolicy>>);
+
+impl Reinforcements {
+    /// The policy of the standard ruleset
+    pub fn classic() -> Self {
+        Reinforcements(vec![
+            Rc::new(TerritoryReinforcements::default()),
+            Rc::new(ContinentReinforcements),
+        ])
+    }
+}
+
+impl Default for Reinforcements {
+    fn default() -> Self {
+        Reinforcements::classic()
+    }
+}
+
+impl ReinforcementPolicy for Reinforcements {
+    fn reinforcements(&self, game: &GameView) -> Vec<Reward> {
+        self.0
+            .iter()
+            .flat_map(|policy| policy.reinforcements(game))
+            .collect()
+    }
+}
+
+/// The same amount of armies every turn
+#[derive(Debug, Clone, Copy)]
+pub struct FixedReinforcements(pub u32);
+
+impl ReinforcementPolicy for FixedReinforcements {
+    fn reinforcements(&self, _game: &GameView) -> Vec<Reward> {
+        vec![(String::from("starting its turn"), self.0)]
+    }
+}
+
+/// One army for every `divisor` occupied territories, with a minimum of `minimum`.
+/// A `divisor` of 0 only rewards the minimum.
+#[derive(Debug, Clone, Copy)]
+pub struct TerritoryReinforcements {
+    pub divisor: u32,
+    pub minimum: u32,
+}
+
+impl Default for TerritoryReinforcements {
+    fn default() -> Self {
+        TerritoryReinforcements {
+            divisor: 3,
+            minimum: 3,
+        }
+    }
+}
+
+impl ReinforcementPolicy for TerritoryReinforcements {
+    fn reinforcements(&self, game: &GameView) -> Vec<Reward> {
+        let territories = game.territories().len() as u32;
+        vec![(
+            format!("occupying {territories} territories"),
+            territories
+                .checked_div(self.divisor)
+                .unwrap_or(0)
+                .max(self.minimum),
+        )]
+    }
+}
+
+/// The territories of the continent occupied by the player of the view
+fn occupied(game: &GameView, continent: &ContinentView) -> u32 {
+    continent
+        .territories
+        .iter()
+        .filter(|territory| game.board.territories[**territory].owner == Some(game.player()))
+        .count() as u32
+}
+
+/// The armies reward of every continent occupied entirely
+#[derive(Debug, Clone, Copy)]
+pub struct ContinentReinforcements;
+
+impl ReinforcementPolicy for ContinentReinforcements {
+    fn reinforcements(&self, game: &GameView) -> Vec<Reward> {
+        game.board
+            .continents
+            .iter()
+            .filter(|continent| occupied(game, continent) == continent.territories.len() as u32)
+            .map(|continent| {
+                (
+                    format!("occupying the entirety of {}", continent.name),
+                    continent.armies_reward,
+                )
+            })
+            .collect()
+    }
+}
+
+/// A part of the armies reward of every continent, relative to the share of its territories
+/// that is occupied. Continents need to be occupied for at least the given `share`.
+#[derive(Debug, Clone, Copy)]
+pub struct PartialContinentReinforcements {
+    pub share: f64,
+}
+
+impl ReinforcementPolicy for PartialContinentReinforcements {
+    fn reinforcements(&self, game: &GameView) -> Vec<Reward> {
+        game.board
+            .continents
+            .iter()
+            .filter_map(|continent| {
+                let share = occupied(game, continent) as f64 / continent.territories.len() as f64;
+                let armies = (continent.armies_reward as f64 * share).floor() as u32;
+                if share < self.share || armies == 0 {
+                    return None;
+                }
+                Some((
+                    format!("occupying {:.0}% of {}", share * 100.0, continent.name),
+                    armies,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// One army for every `divisor` territories in the largest group of connected territories.
+/// A `divisor` of 0 rewards nothing.
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectedReinforcements {
+    pub divisor: u32,
+}
+
+impl ReinforcementPolicy for ConnectedReinforcements {
+    fn reinforcements(&self, game: &GameView) -> Vec<Reward> {
+        let territories = &game.board.territories;
+        let owned = |index: usize| territories[index].owner == Some(game.player());
+
+        let mut visited = vec![false; territories.len()];
+        let mut largest: u32 = 0;
+        for start in game.territories() {
+            if visited[start.index] {
+                continue;
+            }
+            visited[start.index] = true;
+            let mut size = 0;
+            let mut queue = VecDeque::from([start.index]);
+            while let Some(index) = queue.pop_front() {
+                size += 1;
+                for adjacent in &territories[index].connections {
+                    if owned(*adjacent) && !visited[*adjacent] {
+                        visited[*adjacent] = true;
+                        queue.push_back(*adjacent);
+                    }
+                }
+            }
+            largest = largest.max(size);
+        }
+
+        vec![(
+            format!("{largest} connected territories"),
+            largest.checked_div(self.divisor).unwrap_or(0),
+        )]
+    }
+}
+
+/// The armies reward of every continent occupied entirely,
+/// decreasing by one for every `decay` turns of the player the continent has been held.
+/// A `decay` of 0 never decreases the reward.
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressiveContinentReinforcements {
+    pub decay: u32,
+}
+
+impl ProgressiveContinentReinforcements {
+    /// The amount of consecutive turns of the player every continent has been held,
+    /// replayed from the history of the game
+    fn turns_held(game: &GameView) -> Vec<u32> {
+        let mut owners = vec![None; game.board.territories.len()];
+        let mut held = vec![0; game.board.continents.len()];
+        for event in game.history.iter() {
+            match event {
+                Event::Claim { player, territory }
+                | Event::Capture {
+                    player, territory, ..
+                } => owners[*territory] = Some(*player),
+                Event::Turn { player, .. } if *player == game.player() => {
+                    for continent in &game.board.continents {
+                        let entirely = continent
+                            .territories
+                            .iter()
+                            .all(|territory| owners[*territory] == Some(game.player()));
+                        held[continent.index] = if entirely {
+                            held[continent.index] + 1
+                        } else {
+                            0
+                        };
+                    }
+                }
+                _ => {}
+            }
+        }
+        held
+    }
+}
+
+impl ReinforcementPolicy for ProgressiveContinentReinforcements {
+    fn reinforcements(&self, game: &GameView) -> Vec<Reward> {
+        let held = ProgressiveContinentReinforcements::turns_held(game);
+        game.board
+            .continents
+            .iter()
+            .filter(|continent| occupied(game, continent) == continent.territories.len() as u32)
+            .map(|continent| {
+                let turns = held[continent.index].max(1);
+                (
+                    format!(
+                        "holding the entirety of {} for {turns} turns",
+                        continent.name
+                    ),
+                    continent
+                        .armies_reward
+                        .saturating_sub((turns - 1).checked_div(self.decay).unwrap_or(0)),
+                )
+            })
+            .collect()
+    }
+}
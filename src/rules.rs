@@ -0,0 +1,183 @@
+//! Optional rules and variants of the game.
+//! The default rules follow the standard ruleset.
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::dice::{DiceSource, FairDice};
+use crate::panics::PanicFallback;
+use crate::reinforcements::{ReinforcementPolicy, Reinforcements};
+use crate::victory::{StalemateRules, Tiebreak, VictoryCondition};
+
+/// The set of optional rules a `Game` is played with
+#[derive(Debug, Clone)]
+pub struct Rules {
+    /// Every player is dealt a hidden mission during setup.
+    /// The first player to fulfill its mission wins the game.
+    pub secret_missions: bool,
+    /// Limits what players can see of the board
+    pub fog_of_war: FogOfWar,
+    /// The defender decides on its dice after seeing the roll of the attacker
+    pub defender_sees_roll: bool,
+    /// The source of the dice rolled in battles
+    pub dice: Rc<dyn DiceSource>,
+    /// Players earn energy every turn to buy commanders with, as in Risk 2210
+    pub commanders: bool,
+    /// Decides how many armies players receive at the start of their turn
+    pub reinforcements: Rc<dyn ReinforcementPolicy>,
+    /// Decides the order in which players claim territories and take their turns
+    pub turn_order: TurnOrder,
+    /// The claim order reverses after every player has claimed a territory: 1, 2, 3, 3, 2, 1, ...
+    pub snake_claim: bool,
+    /// The handicap of every player, by the index of the player.
+    /// Players without a handicap play by the same rules as everyone else.
+    pub handicaps: Vec<Handicap>,
+    /// Additional ways to win the game, next to defeating all other players
+    pub victory_conditions: Vec<VictoryCondition>,
+    /// Ranks the players when the game is stopped at its turn or time limit
+    pub tiebreak: Tiebreak,
+    /// Detects games that are unlikely to ever end
+    pub stalemate: StalemateRules,
+    /// The longest a single decision of a player may take before the player forfeits.
+    /// An `ExternalPlayer` stops waiting for its bot once the time is up and forfeits right away,
+    /// whatever `on_panic` says. Players that run in the same process cannot be interrupted:
+    /// the game waits for their decision and the player forfeits once a late decision returns.
+    pub decision_time: Option<Duration>,
+    /// The total time a player may take for all of its decisions before the player forfeits
+    pub game_time: Option<Duration>,
+    /// What happens to the territories of a player that surrenders or runs out of time
+    pub forfeit: Forfeit,
+    /// What happens when a player panics during a decision
+    pub on_panic: PanicFallback,
+}
+
+impl Rules {
+    /// The handicap of the given player
+    pub fn handicap(&self, player: usize) -> Handicap {
+        self.handicaps.get(player).cloned().unwrap_or_default()
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            secret_missions: false,
+            fog_of_war: FogOfWar::default(),
+            defender_sees_roll: false,
+            dice: Rc::new(FairDice),
+            commanders: false,
+            reinforcements: Rc::new(Reinforcements::classic()),
+            turn_order: TurnOrder::default(),
+            snake_claim: false,
+            handicaps: vec![],
+            victory_conditions: vec![],
+            tiebreak: Tiebreak::default(),
+            stalemate: StalemateRules::default(),
+            decision_time: None,
+            game_time: None,
+            forfeit: Forfeit::default(),
+            on_panic: PanicFallback::default(),
+        }
+    }
+}
+
+/// Limits what a player can see of the board.
+/// Under fog of war a player only sees the exact armies on its own territories
+/// and on the territories adjacent to them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FogOfWar {
+    /// Every player sees the entire board
+    #[default]
+    Disabled,
+    /// The owners of all other territories remain visible
+    Owners,
+    /// Nothing is visible of all other territories
+    Hidden,
+}
+
+/// The order in which players take their turns.
+/// Every round every player that is not defeated takes a single turn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TurnOrder {
+    /// The order of the seats, starting with the first player
+    Fixed,
+    /// The order of the seats, starting with the highest roller of the dice during setup
+    #[default]
+    DiceRoll,
+    /// A random order every round
+    Shuffle,
+    /// The player with the fewest territories goes first every round,
+    /// ties are broken by the armies on the board and then by the lowest player index
+    WeakestFirst,
+}
+
+/// What happens to the territories of a player that forfeits the game
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Forfeit {
+    /// The territories become neutral and keep their armies
+    #[default]
+    Neutral,
+    /// The armies are returned to the box, a single neutral army remains on every territory
+    Remove,
+}
+
+/// Why a player forfeits the game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ForfeitReason {
+    /// The player surrendered at the start of its turn
+    Surrender,
+    /// A single decision took longer than the decision time of the rules
+    DecisionTime,
+    /// All decisions together took longer than the game time of the rules
+    GameTime,
+    /// The player panicked during a decision
+    Panic,
+}
+
+impl Display for ForfeitReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ForfeitReason::Surrender => write!(f, "surrendered"),
+            ForfeitReason::DecisionTime => write!(f, "took too long for a decision"),
+            ForfeitReason::GameTime => write!(f, "ran out of time"),
+            ForfeitReason::Panic => write!(f, "crashed"),
+        }
+    }
+}
+
+/// Asymmetric starting conditions and reinforcements of a single player
+#[derive(Debug, Clone, PartialEq)]
+pub struct Handicap {
+    /// Added to the armies the player starts with, negative to start with fewer armies
+    pub starting_armies: i32,
+    /// Multiplies the reinforcements the player receives every turn
+    pub reinforcement_multiplier: f64,
+    /// Added to the reinforcements every turn, after the multiplier
+    pub reinforcement_bonus: i32,
+    /// The territories the player claims before any free territory is claimed
+    pub territories: Vec<usize>,
+    /// The continents of which the player claims all territories before any free territory is claimed
+    pub continents: Vec<usize>,
+}
+
+impl Handicap {
+    /// The reinforcements after applying the multiplier and the bonus
+    pub fn reinforcements(&self, armies: u32) -> u32 {
+        let armies = (armies as f64 * self.reinforcement_multiplier).round() as i64;
+        (armies + self.reinforcement_bonus as i64).max(0) as u32
+    }
+}
+
+impl Default for Handicap {
+    fn default() -> Self {
+        Handicap {
+            starting_armies: 0,
+            reinforcement_multiplier: 1.0,
+            reinforcement_bonus: 0,
+            territories: vec![],
+            continents: vec![],
+        }
+    }
+}
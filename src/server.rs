@@ -0,0 +1,342 @@
+//! Hosts a game for players that connect over TCP, like bots on an office ladder.
+//! Remote players exchange the same line-delimited JSON messages as an `ExternalPlayer`.
+//!
+//! A client opens the connection with a single line:
+//! - `{"Join": "<name>"}` takes the next free seat, which is answered with
+//!   `{"type": "seat", "seat": 0, "token": "<token>"}`
+//! - `{"Rejoin": "<token>"}` takes back the seat of the token in a game that is in progress,
+//!   after which the client receives the entire history with the message it missed
+//! - `"Spectate"` follows the game, starting with everything that happened so far,
+//!   as `{"type": "event", "event": {...}}` lines and a final `{"type": "game_over", "result": {...}}`
+//!
+//! Clients that are turned away receive `{"type": "error", "message": "..."}`.
+//! A seat that is disconnected keeps its place until a decision times out.
+//! Spectators that stop reading are disconnected, so they cannot hold up the game.
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use colored::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::history::Event;
+use crate::players::{Connection, ExternalPlayer, ReceiveError};
+use crate::victory::GameResult;
+use crate::Game;
+
+/// The colors of the seats, in order
+const COLORS: [(Color, Color); 6] = [
+    (Color::Red, Color::White),
+    (Color::Green, Color::White),
+    (Color::Blue, Color::White),
+    (Color::White, Color::Black),
+    (Color::Magenta, Color::White),
+    (Color::Yellow, Color::Black),
+];
+
+/// How long a write to a spectator may block before the spectator is disconnected
+const SPECTATOR_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The first line a client sends
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Hello {
+    /// Take the next free seat with the given name
+    Join(String),
+    /// Take back the seat of the given token
+    Rejoin(String),
+    /// Follow the game without playing
+    Spectate,
+}
+
+/// The lines the server sends besides the messages of an `ExternalPlayer`
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Notice<'a> {
+    Seat { seat: usize, token: &'a str },
+    Error { message: &'a str },
+    Event { event: &'a Event },
+    GameOver { result: &'a GameResult },
+}
+
+impl Notice<'_> {
+    fn line(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+/// A line received from a client, with the generation of the connection it arrived on
+type Line = (u64, String);
+
+/// A seat at the table, shared between the game and the connections of its client
+struct Seat {
+    name: String,
+    token: String,
+    /// The current connection of the client, `None` while disconnected
+    stream: Mutex<Option<TcpStream>>,
+    /// Counts the connections, so lines of an earlier connection can be told apart
+    generation: Mutex<u64>,
+    lines: Sender<Line>,
+}
+
+/// The connection of the game to a seat
+struct SeatConnection {
+    seat: Arc<Seat>,
+    lines: Receiver<Line>,
+    /// The connection of the client that received the last message
+    generation: Mutex<u64>,
+}
+
+impl Connection for SeatConnection {
+    fn send(&self, line: &dyn Fn(bool) -> String) -> io::Result<()> {
+        while self.lines.try_recv().is_ok() {}
+
+        // A disconnected client receives the message once it rejoins.
+        // A client only rejoins while holding the stream,
+        // so the line goes to the connection it was built for.
+        let mut stream = self.seat.stream.lock().unwrap();
+        let reconnected = self.reconnected();
+        *self.generation.lock().unwrap() = *self.seat.generation.lock().unwrap();
+        let line = line(reconnected);
+        if let Some(stream) = stream.as_mut() {
+            if writeln!(stream, "{line}").is_err() {
+                let _ = stream.shutdown(Shutdown::Both);
+            }
+        }
+        Ok(())
+    }
+
+    fn receive(&self, timeout: Duration) -> Result<String, ReceiveError> {
+        let deadline = Instant::now() + timeout;
+        let sent = *self.generation.lock().unwrap();
+        loop {
+            if self.reconnected() {
+                return Err(ReceiveError::Reconnected);
+            }
+
+            // Wait in short slices to notice when the client rejoins
+            let slice = deadline
+                .saturating_duration_since(Instant::now())
+                .min(Duration::from_millis(50));
+            match self.lines.recv_timeout(slice) {
+                Ok((generation, line)) if generation == sent => return Ok(line),
+                Ok(_) => {}
+                Err(RecvTimeoutError::Timeout) if Instant::now() >= deadline => {
+                    return Err(ReceiveError::Timeout)
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return Err(ReceiveError::Closed),
+            }
+        }
+    }
+}
+
+impl SeatConnection {
+    /// Whether the client connected again since the last line was sent
+    fn reconnected(&self) -> bool {
+        *self.seat.generation.lock().unwrap() != *self.generation.lock().unwrap()
+    }
+}
+
+/// Everything the lobby shares with the game
+#[derive(Default)]
+struct Lobby {
+    seats: Vec<Arc<Seat>>,
+    /// The seats that joined but have not been handed to the game yet
+    joined: Vec<(Arc<Seat>, Receiver<Line>)>,
+    spectators: Vec<TcpStream>,
+    /// Every line sent to the spectators so far
+    feed: Vec<String>,
+}
+
+pub struct Server {
+    address: SocketAddr,
+    capacity: usize,
+    lobby: Arc<(Mutex<Lobby>, Condvar)>,
+}
+
+impl Server {
+    /// Starts accepting clients for a game with the given amount of seats
+    pub fn new(address: impl ToSocketAddrs, seats: usize) -> io::Result<Self> {
+        assert!(
+            (2..=COLORS.len()).contains(&seats),
+            "A game has 2 to 6 players."
+        );
+        let listener = TcpListener::bind(address)?;
+        let server = Server {
+            address: listener.local_addr()?,
+            capacity: seats,
+            lobby: Arc::new((Mutex::new(Lobby::default()), Condvar::new())),
+        };
+
+        let lobby = Arc::clone(&server.lobby);
+        thread::spawn(move || {
+            for stream in listener.incoming().map_while(Result::ok) {
+                let lobby = Arc::clone(&lobby);
+                thread::spawn(move || welcome(stream, &lobby, seats));
+            }
+        });
+        Ok(server)
+    }
+
+    /// The address clients connect to
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Waits until every seat is taken and returns the players in the order they joined.
+    /// Every decision of a player times out after the given duration.
+    pub fn players(&self, timeout: Duration) -> Vec<ExternalPlayer> {
+        let (lobby, joined) = &*self.lobby;
+        let mut lobby = joined
+            .wait_while(lobby.lock().unwrap(), |lobby| {
+                lobby.joined.len() < self.capacity
+            })
+            .unwrap();
+        lobby
+            .joined
+            .drain(..)
+            .enumerate()
+            .map(|(index, (seat, lines))| {
+                let (background, foreground) = COLORS[index];
+                ExternalPlayer::with_connection(
+                    &seat.name,
+                    background,
+                    foreground,
+                    Box::new(SeatConnection {
+                        seat: Arc::clone(&seat),
+                        lines,
+                        generation: Mutex::new(0),
+                    }),
+                    timeout,
+                )
+            })
+            .collect()
+    }
+
+    /// Plays the game that has been set up, streaming every event to the spectators.
+    /// The game is stopped at the given turn limit and ranked by the tiebreak.
+    pub fn play(&self, game: &mut Game, max_turns: Option<u64>) -> GameResult {
+        let mut sent = 0;
+        loop {
+            let more = game.step(false) && max_turns.is_none_or(|max| game.get_turn() < max);
+            sent = self.broadcast(game, sent);
+            if !more {
+                break;
+            }
+        }
+        let result = game.tiebreak();
+        self.broadcast(game, sent);
+        self.publish(Notice::GameOver { result: &result }.line());
+        result
+    }
+
+    /// Sends the events after the given amount of events to the spectators
+    fn broadcast(&self, game: &Game, sent: usize) -> usize {
+        let history = game.get_history().borrow();
+        for event in &history[sent..] {
+            self.publish(Notice::Event { event }.line());
+        }
+        history.len()
+    }
+
+    fn publish(&self, line: String) {
+        let mut lobby = self.lobby.0.lock().unwrap();
+        lobby
+            .spectators
+            .retain_mut(|spectator| writeln!(spectator, "{line}").is_ok());
+        lobby.feed.push(line);
+    }
+}
+
+/// Handles the first line of a new client
+fn welcome(stream: TcpStream, lobby: &(Mutex<Lobby>, Condvar), capacity: usize) {
+    // Every message is a single short line that is waited for
+    let _ = stream.set_nodelay(true);
+    let Ok(reader) = stream.try_clone() else {
+        return;
+    };
+    let mut reader = BufReader::new(reader);
+    let mut line = String::new();
+    if reader.read_line(&mut line).is_err() {
+        return;
+    }
+    let mut writer = stream;
+
+    let (seat, generation) = match serde_json::from_str::<Hello>(&line) {
+        Ok(Hello::Join(name)) => {
+            let mut guard = lobby.0.lock().unwrap();
+            if guard.seats.len() >= capacity {
+                drop(guard);
+                return refuse(&mut writer, "The game is full");
+            }
+            let (sender, lines) = mpsc::channel();
+            let seat = Arc::new(Seat {
+                name,
+                token: format!("{:016x}", rand::random::<u64>()),
+                stream: Mutex::new(writer.try_clone().ok()),
+                generation: Mutex::new(0),
+                lines: sender,
+            });
+            let notice = Notice::Seat {
+                seat: guard.seats.len(),
+                token: &seat.token,
+            };
+            let _ = writeln!(writer, "{}", notice.line());
+            guard.seats.push(Arc::clone(&seat));
+            guard.joined.push((Arc::clone(&seat), lines));
+            lobby.1.notify_all();
+            (seat, 0)
+        }
+        Ok(Hello::Rejoin(token)) => {
+            let guard = lobby.0.lock().unwrap();
+            let Some(seat) = guard.seats.iter().find(|seat| seat.token == token).cloned() else {
+                drop(guard);
+                return refuse(&mut writer, "Unknown token");
+            };
+            drop(guard);
+
+            // The previous connection of the seat is closed
+            let mut stream = seat.stream.lock().unwrap();
+            if let Some(previous) = stream.replace(writer.try_clone().unwrap()) {
+                let _ = previous.shutdown(Shutdown::Both);
+            }
+            let generation = {
+                let mut generation = seat.generation.lock().unwrap();
+                *generation += 1;
+                *generation
+            };
+            drop(stream);
+            (seat, generation)
+        }
+        Ok(Hello::Spectate) => {
+            // Spectators are written to while holding the lobby
+            if writer.set_write_timeout(Some(SPECTATOR_TIMEOUT)).is_err() {
+                return;
+            }
+            let mut guard = lobby.0.lock().unwrap();
+            for line in &guard.feed {
+                if writeln!(writer, "{line}").is_err() {
+                    return;
+                }
+            }
+            guard.spectators.push(writer);
+            return;
+        }
+        Err(_) => return refuse(&mut writer, "Expected Join, Rejoin or Spectate"),
+    };
+
+    // Replies of the client are forwarded to the game
+    for line in reader.lines().map_while(Result::ok) {
+        if seat.lines.send((generation, line)).is_err() {
+            break;
+        }
+    }
+}
+
+/// Turns a client away
+fn refuse(stream: &mut TcpStream, message: &str) {
+    let _ = writeln!(stream, "{}", Notice::Error { message }.line());
+}
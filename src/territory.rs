@@ -4,10 +4,69 @@ use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::rc::{Rc, Weak};
 
-use itertools::enumerate;
+use itertools::{enumerate, join};
+use serde::Serialize;
 
+use crate::commanders::Commander;
 use crate::continent::Continent;
-use crate::players::Player;
+use crate::modifiers::Modifier;
+use crate::players::{Player, Seat};
+
+/// A territory held by neutral armies instead of a player, as defined by the board.
+/// Neutral territories can never be claimed, but they can be conquered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Neutral {
+    /// The armies the territory starts with
+    pub armies: u32,
+    /// Impassable territories can never be attacked
+    pub impassable: bool,
+}
+
+/// The type of a connection from one territory to another.
+/// Connections are directed, a connection that is only created on one side is one-way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum ConnectionType {
+    /// Allows attacking and fortifying
+    #[default]
+    Land,
+    /// Allows fortifying and attacking with at most 2 dice
+    Sea,
+    /// Only allows fortifying
+    FortifyOnly,
+    /// Only allows attacking
+    AttackOnly,
+}
+
+impl ConnectionType {
+    pub fn allows_attack(&self) -> bool {
+        *self != ConnectionType::FortifyOnly
+    }
+
+    pub fn allows_fortify(&self) -> bool {
+        *self != ConnectionType::AttackOnly
+    }
+
+    /// The maximum amount of dice the attacker may roll over the connection
+    pub fn max_attack_dice(&self) -> u32 {
+        match self {
+            ConnectionType::Land | ConnectionType::AttackOnly => 3,
+            ConnectionType::Sea => 2,
+            ConnectionType::FortifyOnly => 0,
+        }
+    }
+}
+
+impl Display for ConnectionType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ConnectionType::Land => "land",
+            ConnectionType::Sea => "sea",
+            ConnectionType::FortifyOnly => "fortify only",
+            ConnectionType::AttackOnly => "attack only",
+        };
+        write!(f, "{name}")
+    }
+}
 
 #[derive(Debug)]
 /// Represents a singular Risk Territory.
@@ -16,9 +75,23 @@ pub struct Territory {
     pub index: RefCell<usize>,
     pub name: String,
     pub abbr: String,
+    /// A name given to the territory during a campaign
+    pub title: RefCell<Option<String>>,
     pub connections: RefCell<Vec<Weak<Territory>>>,
+    /// The types of the connections, in the same order as `connections`
+    /// Missing types are `ConnectionType::Land`
+    pub connection_types: RefCell<Vec<ConnectionType>>,
     pub continent: Rc<Continent>,
     pub armies: RefCell<u32>,
+    /// The commanders stationed on the territory, next to its armies
+    pub commanders: RefCell<Vec<Commander>>,
+    /// Whether the territory is neutral, as defined by the board or after its owner forfeited
+    pub neutral: RefCell<Option<Neutral>>,
+    /// The modifiers of the territory, like fortresses and cities
+    pub modifiers: RefCell<Vec<Modifier>>,
+    /// The place of the territory on a drawing of the board, measured from the top left.
+    /// Boards without positions are laid out automatically.
+    pub position: RefCell<Option<(f64, f64)>>,
     player: RefCell<Option<Weak<dyn Player>>>,
 }
 
@@ -28,21 +101,77 @@ impl Territory {
             index: RefCell::from(0),
             name: String::from(name),
             abbr: String::from(&name[0..min(5, name.len())]).to_uppercase(),
+            title: RefCell::from(None),
             connections: RefCell::from(vec![]),
+            connection_types: RefCell::from(vec![]),
             continent,
             armies: RefCell::from(0),
+            commanders: RefCell::from(vec![]),
+            neutral: RefCell::from(None),
+            modifiers: RefCell::from(vec![]),
+            position: RefCell::from(None),
             player: RefCell::from(None),
         }
     }
 
+    /// Creates a neutral territory starting with the armies of `neutral`
+    pub fn new_neutral(name: &str, continent: Rc<Continent>, neutral: Neutral) -> Self {
+        Territory {
+            armies: RefCell::from(neutral.armies),
+            neutral: RefCell::from(Some(neutral)),
+            ..Territory::new(name, continent)
+        }
+    }
+
+    /// Whether the territory is still held by neutral armies
+    pub fn is_neutral(&self) -> bool {
+        self.neutral.borrow().is_some() && self.get_player().is_none()
+    }
+
+    /// Whether the territory can never be attacked
+    pub fn is_impassable(&self) -> bool {
+        self.neutral
+            .borrow()
+            .is_some_and(|neutral| neutral.impassable)
+    }
+
     /// Creates the connections to the given territories
     pub fn create_connections(&self, connections: Vec<&Rc<Territory>>) {
+        self.create_typed_connections(
+            connections
+                .into_iter()
+                .map(|territory| (territory, ConnectionType::Land))
+                .collect(),
+        );
+    }
+
+    /// Creates the connections of the given types to the given territories
+    pub fn create_typed_connections(&self, connections: Vec<(&Rc<Territory>, ConnectionType)>) {
         *self.connections.borrow_mut() = connections
             .iter()
-            .map(|territory| Rc::downgrade(territory))
+            .map(|(territory, _)| Rc::downgrade(territory))
+            .collect();
+        *self.connection_types.borrow_mut() = connections
+            .iter()
+            .map(|(_, connection_type)| *connection_type)
             .collect();
     }
 
+    /// The type of the connection to the given territory, `None` if there is no connection
+    pub fn connection_to(&self, territory: &Territory) -> Option<ConnectionType> {
+        self.connections
+            .borrow()
+            .iter()
+            .position(|adjacent| adjacent.upgrade().as_deref() == Some(territory))
+            .map(|i| {
+                self.connection_types
+                    .borrow()
+                    .get(i)
+                    .copied()
+                    .unwrap_or_default()
+            })
+    }
+
     pub fn get_player(&self) -> Option<Rc<dyn Player>> {
         if let Some(weak) = &*self.player.borrow() {
             let player = weak.upgrade();
@@ -55,9 +184,9 @@ impl Territory {
         *self.player.borrow_mut() = player;
     }
 
-    /// Places given amount from armies on the territory and removes them from the player
+    /// Places given amount from armies on the territory and removes them from the seat of the player
     /// Territory must be owned by the player or not owned at all
-    pub fn place_armies(&self, player: Rc<dyn Player>, armies: u32) {
+    pub fn place_armies(&self, player: Rc<dyn Player>, seat: &Seat, armies: u32) {
         let player = player.get_state();
         if let Some(occupant) = self.get_player() {
             assert_eq!(
@@ -67,12 +196,12 @@ impl Territory {
             );
         }
         assert!(
-            *player.armies.borrow() >= armies,
+            *seat.armies.borrow() >= armies,
             "The player does not have enough armies available"
         );
 
         *self.armies.borrow_mut() += armies;
-        *player.armies.borrow_mut() -= armies;
+        *seat.armies.borrow_mut() -= armies;
     }
 }
 
@@ -81,12 +210,20 @@ impl Display for Territory {
         let mut connections = vec![];
         for territory in &*self.connections.borrow() {
             if let Some(territory) = territory.upgrade() {
-                connections.push(String::from(&territory.name));
+                let mut connection = String::from(&territory.name);
+                let connection_type = self.connection_to(&territory).unwrap_or_default();
+                if connection_type != ConnectionType::Land {
+                    connection += &format!(" ({connection_type})");
+                }
+                if territory.connection_to(self).is_none() {
+                    connection += " (one-way)";
+                }
+                connections.push(connection);
             }
         }
         let connections = connections.join(", ");
 
-        let mut player_name = String::from("None");
+        let mut player_name = String::from(if self.is_neutral() { "Neutral" } else { "None" });
         if let Some(player) = self.get_player() {
             player_name = String::from(&player.get_state().name);
         }
@@ -94,16 +231,22 @@ impl Display for Territory {
         write!(
             f,
             "{}\n\
+            \ttitle: {}\n\
             \tid: {}\n\
             \tcontinent: {}\n\
             \tconnections: {}\n\
             \tarmies: {}\n\
+            \tcommanders: {}\n\
+            \tmodifiers: {}\n\
             \tplayer: {}\n",
             self.name,
+            self.title.borrow().as_deref().unwrap_or("None"),
             self.index.borrow(),
             self.continent.name,
             connections,
             self.armies.borrow(),
+            join(&*self.commanders.borrow(), ", "),
+            join(&*self.modifiers.borrow(), ", "),
             player_name
         )
     }
@@ -0,0 +1,787 @@
+//! A full-screen terminal interface to follow a game and to play it as a human.
+//!
+//! The screen shows the territories of every continent with their owners and armies,
+//! a panel for every player and the events of the game as they happen.
+//! The engine has no cards, so the player panels show the energy to buy commanders instead.
+//! Under fog of war the screen shows the game as the first human player sees it.
+//!
+//! | key                  | while watching                       | while deciding as a `HumanPlayer`  |
+//! |----------------------|--------------------------------------|------------------------------------|
+//! | `space`              | pause or resume the game             |                                    |
+//! | `n` or `→`           | play a single phase of a paused game |                                    |
+//! | `+` and `-`          | play faster or slower                |                                    |
+//! | `↑` and `↓`          | scroll through the events            | move the cursor or change a number |
+//! | `PgUp` and `PgDn`    | scroll through the events            | scroll through the events          |
+//! | `enter`              |                                      | confirm                            |
+//! | `esc`                |                                      | skip or go back, where possible    |
+//! | `q`                  | stop the game                        |                                    |
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::{self, Stdout};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use colored::Color as TerminalColor;
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::crossterm::event::{self, Event as TerminalEvent, KeyCode, KeyEventKind};
+use ratatui::crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::crossterm::{cursor, execute};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{Frame, Terminal};
+
+use crate::history::Event;
+use crate::players::{HumanPlayer, PlayerType};
+use crate::rules::FogOfWar;
+use crate::victory::GameResult;
+use crate::view::GameView;
+use crate::Game;
+
+/// The delays between two phases, from fast to slow
+const DELAYS: [Duration; 7] = [
+    Duration::ZERO,
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+];
+
+/// The source of the key presses of the interface
+pub trait Keys {
+    /// Waits for at most the given duration for the next key press
+    fn next(&mut self, timeout: Duration) -> Option<KeyCode>;
+
+    /// Whether no key will ever be pressed again
+    fn exhausted(&self) -> bool {
+        false
+    }
+}
+
+/// The keyboard of the terminal
+struct TerminalKeys;
+
+impl Keys for TerminalKeys {
+    fn next(&mut self, timeout: Duration) -> Option<KeyCode> {
+        let deadline = Instant::now() + timeout;
+        while event::poll(deadline.saturating_duration_since(Instant::now())).ok()? {
+            if let Ok(TerminalEvent::Key(key)) = event::read() {
+                if key.kind == KeyEventKind::Press {
+                    return Some(key.code);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Key presses that are scripted in advance, e.g. for tests.
+/// Once the script has run out a running game continues,
+/// but a question panics and a paused game stops.
+impl Keys for VecDeque<KeyCode> {
+    fn next(&mut self, _timeout: Duration) -> Option<KeyCode> {
+        self.pop_front()
+    }
+
+    fn exhausted(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+/// What a human player is asked
+pub(crate) enum Prompt<'a> {
+    /// Pick one of the given territories
+    Territory(&'a [usize], usize),
+    /// Pick a number in the given range
+    Amount(u32, u32, u32),
+    /// Pick one of the given options
+    Choice(&'a [String], usize),
+}
+
+/// The terminal shared by the interface and the human players
+pub(crate) struct Screen<B: Backend> {
+    terminal: RefCell<Terminal<B>>,
+    keys: RefCell<Box<dyn Keys>>,
+    /// The colors of the players, by index
+    colors: RefCell<Vec<(Color, Color)>>,
+    /// How many events the log is scrolled back
+    scroll: RefCell<usize>,
+}
+
+impl<B: Backend> Screen<B> {
+    /// Waits for at most the given duration for the next key press
+    fn key(&self, timeout: Duration) -> Option<KeyCode> {
+        self.keys.borrow_mut().next(timeout)
+    }
+
+    /// Whether no key will ever be pressed again
+    fn exhausted(&self) -> bool {
+        self.keys.borrow().exhausted()
+    }
+
+    /// Scrolls the event log if the key is meant for it
+    fn scroll(&self, key: KeyCode, step: usize) -> bool {
+        let mut scroll = self.scroll.borrow_mut();
+        match key {
+            KeyCode::PageUp => *scroll += 10,
+            KeyCode::PageDown => *scroll = scroll.saturating_sub(10),
+            KeyCode::Up if step > 0 => *scroll += step,
+            KeyCode::Down if step > 0 => *scroll = scroll.saturating_sub(step),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Asks the human player and waits for its answer.
+    /// Returns the index of the picked territory, option or the picked amount,
+    /// or `None` when the question is skipped with `esc`.
+    pub(crate) fn ask(
+        &self,
+        game: &GameView,
+        question: &str,
+        mut prompt: Prompt,
+        optional: bool,
+    ) -> Option<usize> {
+        loop {
+            let title = format!("{} decides", game.players[game.player()].name);
+            let cursor = match prompt {
+                Prompt::Territory(territories, cursor) => Some(territories[cursor]),
+                _ => None,
+            };
+            self.draw(
+                game,
+                &title,
+                |frame, area| prompt_lines(frame, area, question, &prompt, optional),
+                cursor,
+            );
+
+            let Some(key) = self.key(Duration::from_millis(250)) else {
+                assert!(!self.exhausted(), "Ran out of keys to answer: {question}");
+                continue;
+            };
+            if self.scroll(key, 0) {
+                continue;
+            }
+            match (&mut prompt, key) {
+                (_, KeyCode::Esc) if optional => return None,
+                (Prompt::Territory(_, cursor) | Prompt::Choice(_, cursor), KeyCode::Enter) => {
+                    return Some(*cursor)
+                }
+                (Prompt::Amount(_, _, value), KeyCode::Enter) => return Some(*value as usize),
+                (Prompt::Territory(options, cursor), KeyCode::Down | KeyCode::Right) => {
+                    *cursor = (*cursor + 1) % options.len()
+                }
+                (Prompt::Choice(options, cursor), KeyCode::Down | KeyCode::Right) => {
+                    *cursor = (*cursor + 1) % options.len()
+                }
+                (Prompt::Territory(options, cursor), KeyCode::Up | KeyCode::Left) => {
+                    *cursor = (*cursor + options.len() - 1) % options.len()
+                }
+                (Prompt::Choice(options, cursor), KeyCode::Up | KeyCode::Left) => {
+                    *cursor = (*cursor + options.len() - 1) % options.len()
+                }
+                (Prompt::Amount(_, max, value), KeyCode::Up | KeyCode::Right) => {
+                    *value = (*value + 1).min(*max)
+                }
+                (Prompt::Amount(min, _, value), KeyCode::Down | KeyCode::Left) => {
+                    *value = value.saturating_sub(1).max(*min)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Draws the entire screen, with the given footer below the map.
+    /// The cursor marks a territory on the map.
+    fn draw(
+        &self,
+        game: &GameView,
+        title: &str,
+        footer: impl FnOnce(&mut Frame, Rect),
+        cursor: Option<usize>,
+    ) {
+        let colors = self.colors.borrow();
+        let scroll = *self.scroll.borrow();
+        let _ = self.terminal.borrow_mut().draw(|frame| {
+            let [main, bottom] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(5)]).areas(frame.area());
+            let [map, side] =
+                Layout::horizontal([Constraint::Min(0), Constraint::Length(48)]).areas(main);
+            let [players, log] = Layout::vertical([
+                Constraint::Length(game.players.len() as u16 * 3 + 2),
+                Constraint::Min(0),
+            ])
+            .areas(side);
+
+            draw_map(frame, map, game, title, &colors, cursor);
+            draw_players(frame, players, game, &colors);
+            draw_log(frame, log, game, scroll);
+            footer(frame, bottom);
+        });
+    }
+}
+
+/// Watches a game in the terminal
+pub struct Tui<B: Backend = CrosstermBackend<Stdout>> {
+    screen: Rc<Screen<B>>,
+    paused: RefCell<bool>,
+    /// The index of the current delay between two phases
+    speed: RefCell<usize>,
+    /// Whether the terminal is restored once the interface is dropped
+    restore: bool,
+}
+
+impl Tui {
+    /// Takes over the terminal until the interface is dropped
+    pub fn new() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, cursor::Hide)?;
+        let terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+        let mut tui = Tui::with_terminal(terminal, Box::new(TerminalKeys));
+        tui.restore = true;
+        Ok(tui)
+    }
+}
+
+impl<B: Backend> Tui<B> {
+    /// Draws to the given terminal and reads the given keys, e.g. for tests
+    pub fn with_terminal(terminal: Terminal<B>, keys: Box<dyn Keys>) -> Self {
+        Tui {
+            screen: Rc::new(Screen {
+                terminal: RefCell::from(terminal),
+                keys: RefCell::from(keys),
+                colors: RefCell::from(vec![]),
+                scroll: RefCell::from(0),
+            }),
+            paused: RefCell::from(false),
+            speed: RefCell::from(3),
+            restore: false,
+        }
+    }
+
+    pub fn get_terminal(&self) -> &RefCell<Terminal<B>> {
+        &self.screen.terminal
+    }
+
+    /// A human player that makes its decisions on this screen
+    pub fn human(
+        &self,
+        name: &str,
+        background: TerminalColor,
+        foreground: TerminalColor,
+    ) -> HumanPlayer<B> {
+        HumanPlayer::new(name, background, foreground, Rc::clone(&self.screen))
+    }
+
+    /// Sets up the game, during which the human players claim their territories
+    pub fn setup(&self, game: &mut Game) {
+        self.use_colors(game);
+        game.setup(false);
+    }
+
+    /// Plays the game that has been set up until it is over or stopped with `q`.
+    /// The game is stopped at the given turn limit and ranked by the tiebreak.
+    pub fn play(&self, game: &mut Game, max_turns: Option<u64>) -> GameResult {
+        self.use_colors(game);
+        loop {
+            if !self.controls(game) {
+                break;
+            }
+            let more = game.step(false) && max_turns.is_none_or(|max| game.get_turn() < max);
+            if !more {
+                break;
+            }
+        }
+        let result = game.tiebreak();
+        let ranking = format!(
+            "Game over, {} ranks first: {}.",
+            game.get_players()[result.first()].get_state().name,
+            result.victory
+        );
+        self.draw(game, &ranking);
+        result
+    }
+
+    /// Shows the players of the game in their colors
+    fn use_colors(&self, game: &Game) {
+        *self.screen.colors.borrow_mut() = game
+            .get_players()
+            .iter()
+            .map(|player| {
+                let (background, foreground) = player.get_state().colors();
+                (color(background), color(foreground))
+            })
+            .collect();
+    }
+
+    /// Waits for any key press, e.g. to keep the final position on the screen
+    pub fn wait(&self) {
+        while self.screen.key(Duration::from_secs(1)).is_none() && !self.screen.exhausted() {}
+    }
+
+    /// Handles the key presses until the next phase may be played.
+    /// Returns `false` when the game is stopped.
+    fn controls(&self, game: &Game) -> bool {
+        let deadline = Instant::now() + DELAYS[*self.speed.borrow()];
+        loop {
+            self.draw(game, "");
+            let paused = *self.paused.borrow();
+            let timeout = if paused {
+                Duration::from_millis(250)
+            } else {
+                deadline.saturating_duration_since(Instant::now())
+            };
+            match self.screen.key(timeout) {
+                Some(KeyCode::Char('q')) => return false,
+                Some(KeyCode::Char(' ')) => *self.paused.borrow_mut() = !paused,
+                Some(KeyCode::Char('n') | KeyCode::Right) if paused => return true,
+                Some(KeyCode::Char('+')) => {
+                    let mut speed = self.speed.borrow_mut();
+                    *speed = speed.saturating_sub(1)
+                }
+                Some(KeyCode::Char('-')) => {
+                    let mut speed = self.speed.borrow_mut();
+                    *speed = (*speed + 1).min(DELAYS.len() - 1)
+                }
+                Some(key) => {
+                    self.screen.scroll(key, 1);
+                }
+                None if !paused => return true,
+                // Nothing could ever resume the game
+                None if self.screen.exhausted() => return false,
+                None => {}
+            }
+            if !*self.paused.borrow() && Instant::now() >= deadline {
+                return true;
+            }
+        }
+    }
+
+    /// Draws the game as the first human player sees it, or as everyone sees it without one,
+    /// with the given message below it
+    fn draw(&self, game: &Game, message: &str) {
+        let human = game
+            .get_players()
+            .iter()
+            .map(|player| player.get_state())
+            .find(|state| state.player_type == PlayerType::HumanPlayer);
+        let (player, fog) = match human {
+            Some(state) => (state.index(), game.get_rules().fog_of_war),
+            None => (0, FogOfWar::Disabled),
+        };
+        let history = game.get_history().borrow();
+        let mut view = GameView::new(
+            game.get_board(),
+            game.get_players(),
+            game.get_seats(),
+            player,
+            fog,
+            game.get_turn(),
+            &history,
+        );
+        // Only a human player gets to see its own secret mission
+        if human.is_none() {
+            view.mission = None;
+        }
+        let title = match game.get_current_player() {
+            Some(player) => format!(
+                "Round {}, {}: {}",
+                game.get_round(),
+                game.get_players()[player].get_state().name,
+                game.get_phase()
+            ),
+            None => format!("Round {}", game.get_round()),
+        };
+        let status = format!(
+            "{}, {} ms between phases",
+            if *self.paused.borrow() {
+                "Paused"
+            } else {
+                "Playing"
+            },
+            DELAYS[*self.speed.borrow()].as_millis()
+        );
+        self.screen.draw(
+            &view,
+            &title,
+            |frame, area| {
+                let lines = vec![
+                    Line::from(status),
+                    Line::from(String::from(message)),
+                    Line::from(
+                        "space pause/resume, n step, +/- speed, arrows/PgUp/PgDn scroll, q quit",
+                    )
+                    .style(Style::new().fg(Color::DarkGray)),
+                ];
+                frame.render_widget(Paragraph::new(lines).block(Block::bordered()), area);
+            },
+            None,
+        );
+    }
+}
+
+impl<B: Backend> Drop for Tui<B> {
+    fn drop(&mut self) {
+        if self.restore {
+            let _ = terminal::disable_raw_mode();
+            let _ = execute!(io::stdout(), LeaveAlternateScreen, cursor::Show);
+        }
+    }
+}
+
+/// The colors of the `colored` crate on the screen
+fn color(color: TerminalColor) -> Color {
+    match color {
+        TerminalColor::Black => Color::Black,
+        TerminalColor::Red => Color::Red,
+        TerminalColor::Green => Color::Green,
+        TerminalColor::Yellow => Color::Yellow,
+        TerminalColor::Blue => Color::Blue,
+        TerminalColor::Magenta => Color::Magenta,
+        TerminalColor::Cyan => Color::Cyan,
+        TerminalColor::White => Color::Gray,
+        TerminalColor::BrightBlack => Color::DarkGray,
+        TerminalColor::BrightRed => Color::LightRed,
+        TerminalColor::BrightGreen => Color::LightGreen,
+        TerminalColor::BrightYellow => Color::LightYellow,
+        TerminalColor::BrightBlue => Color::LightBlue,
+        TerminalColor::BrightMagenta => Color::LightMagenta,
+        TerminalColor::BrightCyan => Color::LightCyan,
+        TerminalColor::BrightWhite => Color::White,
+        TerminalColor::TrueColor { r, g, b } => Color::Rgb(r, g, b),
+    }
+}
+
+/// The style of a territory of the given owner
+fn owner_style(owner: Option<usize>, colors: &[(Color, Color)]) -> Style {
+    match owner.and_then(|owner| colors.get(owner)) {
+        Some((background, foreground)) => Style::new().bg(*background).fg(*foreground),
+        None => Style::new().fg(Color::DarkGray),
+    }
+}
+
+/// The territories of every continent, in as many columns as needed.
+/// Every territory is numbered and followed by the numbers of the territories it connects to,
+/// unless those do not fit. The territories connected to the cursor are underlined.
+fn draw_map(
+    frame: &mut Frame,
+    area: Rect,
+    game: &GameView,
+    title: &str,
+    colors: &[(Color, Color)],
+    cursor: Option<usize>,
+) {
+    let mut block = Block::bordered().title(format!(" {title} "));
+    if let Some(mission) = &game.mission {
+        block = block.title_bottom(format!(" Mission: {mission} "));
+    }
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let (columns, width) = match map_columns(game, colors, cursor, inner.height, true) {
+        (columns, width) if columns.len() * width > inner.width as usize => {
+            map_columns(game, colors, cursor, inner.height, false)
+        }
+        fitting => fitting,
+    };
+    for (index, column) in columns.into_iter().enumerate() {
+        let x = inner.x + (index * width) as u16;
+        if x >= inner.right() {
+            break;
+        }
+        let area = Rect {
+            x,
+            width: (width as u16).min(inner.right() - x),
+            ..inner
+        };
+        frame.render_widget(Paragraph::new(column), area);
+    }
+}
+
+/// The lines of the map in columns of the given height, and the width of a column
+fn map_columns<'a>(
+    game: &'a GameView,
+    colors: &[(Color, Color)],
+    cursor: Option<usize>,
+    height: u16,
+    neighbours: bool,
+) -> (Vec<Vec<Line<'a>>>, usize) {
+    let territories = &game.board.territories;
+    let digits = territories.len().saturating_sub(1).to_string().len();
+    let name = territories
+        .iter()
+        .map(|territory| territory.name.len())
+        .max()
+        .unwrap_or(0);
+    let connections = match neighbours {
+        true => territories
+            .iter()
+            .map(|territory| territory.connections.len() * (digits + 1))
+            .max()
+            .unwrap_or(0),
+        false => 0,
+    };
+    let width = digits + name + connections + 11;
+    let adjacent = cursor.map_or(&[][..], |cursor| &territories[cursor].connections[..]);
+
+    let mut columns: Vec<Vec<Line>> = vec![vec![]];
+    for continent in &game.board.continents {
+        let mut lines =
+            vec![
+                Line::from(format!("{} +{}", continent.name, continent.armies_reward))
+                    .style(Style::new().add_modifier(Modifier::BOLD)),
+            ];
+        for territory in &continent.territories {
+            let territory = &territories[*territory];
+            let armies = match (territory.impassable, territory.armies) {
+                (true, _) => String::from("##"),
+                (false, Some(armies)) => armies.to_string(),
+                (false, None) => String::from("?"),
+            };
+            let commanders: String = territory
+                .commanders
+                .iter()
+                .flatten()
+                .map(|commander| commander.initial())
+                .collect();
+            let marker = if cursor == Some(territory.index) {
+                "> "
+            } else {
+                "  "
+            };
+            let mut style = owner_style(territory.owner, colors);
+            if cursor == Some(territory.index) {
+                style = style.add_modifier(Modifier::REVERSED);
+            } else if adjacent.contains(&territory.index) {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            let mut spans = vec![
+                Span::raw(marker),
+                Span::styled(
+                    format!(
+                        "{:>digits$} {:<name$}{commanders:>3}{armies:>4} ",
+                        territory.index, territory.name
+                    ),
+                    style,
+                ),
+            ];
+            if neighbours {
+                let connections: Vec<String> = territory
+                    .connections
+                    .iter()
+                    .map(|adjacent| adjacent.to_string())
+                    .collect();
+                spans.push(Span::styled(
+                    format!(" {}", connections.join(" ")),
+                    Style::new().fg(Color::DarkGray),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::default());
+
+        let column = columns.last_mut().unwrap();
+        if !column.is_empty() && column.len() + lines.len() > height as usize {
+            columns.push(lines);
+        } else {
+            column.extend(lines);
+        }
+    }
+    (columns, width)
+}
+
+/// The territories, armies, reserve, energy and continents of every player
+fn draw_players(frame: &mut Frame, area: Rect, game: &GameView, colors: &[(Color, Color)]) {
+    let current = game.history.iter().rev().find_map(|event| match event {
+        Event::Turn { player, .. } => Some(*player),
+        _ => None,
+    });
+    let mut lines = vec![];
+    for player in &game.players {
+        let territories: Vec<_> = game
+            .board
+            .territories
+            .iter()
+            .filter(|territory| territory.owner == Some(player.index))
+            .collect();
+        let armies: u32 = territories
+            .iter()
+            .filter_map(|territory| territory.armies)
+            .sum();
+        let continents: Vec<&str> =
+            game.board
+                .continents
+                .iter()
+                .filter(|continent| {
+                    continent.territories.iter().all(|territory| {
+                        game.board.territories[*territory].owner == Some(player.index)
+                    })
+                })
+                .map(|continent| &continent.name[..])
+                .collect();
+
+        let mut name = vec![Span::styled(
+            format!(" {} ", player.name),
+            owner_style(Some(player.index), colors),
+        )];
+        if player.defeated {
+            name.push(Span::raw(" defeated"));
+        } else if current == Some(player.index) {
+            name.push(Span::raw(" to play"));
+        }
+        lines.push(Line::from(name));
+        lines.push(Line::from(format!(
+            "  {} territories, {armies} armies, {} in reserve",
+            territories.len(),
+            player.reserve,
+        )));
+        lines.push(Line::from(format!(
+            "  {} energy  {}",
+            player.energy,
+            continents.join(", ")
+        )));
+    }
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(" Players ")),
+        area,
+    );
+}
+
+/// The most recent events, or earlier events when scrolled back
+fn draw_log(frame: &mut Frame, area: Rect, game: &GameView, scroll: usize) {
+    let height = area.height.saturating_sub(2) as usize;
+    let end = game
+        .history
+        .len()
+        .saturating_sub(scroll)
+        .max(height.min(game.history.len()));
+    let start = end.saturating_sub(height);
+    let lines: Vec<Line> = game.history[start..end]
+        .iter()
+        .map(|event| Line::from(describe(event, game)))
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(" Events ")),
+        area,
+    );
+}
+
+/// The question and its answers
+fn prompt_lines(frame: &mut Frame, area: Rect, question: &str, prompt: &Prompt, optional: bool) {
+    let answer = match prompt {
+        Prompt::Territory(..) => Line::from("Move the cursor on the map with the arrows"),
+        Prompt::Amount(min, max, value) => Line::from(vec![
+            Span::raw(format!("{min} <  ")),
+            Span::styled(
+                value.to_string(),
+                Style::new().add_modifier(Modifier::REVERSED),
+            ),
+            Span::raw(format!("  > {max}")),
+        ]),
+        Prompt::Choice(options, cursor) => Line::from(
+            options
+                .iter()
+                .enumerate()
+                .flat_map(|(index, option)| {
+                    let style = if index == *cursor {
+                        Style::new().add_modifier(Modifier::REVERSED)
+                    } else {
+                        Style::new()
+                    };
+                    [Span::styled(format!(" {option} "), style), Span::raw(" ")]
+                })
+                .collect::<Vec<Span>>(),
+        ),
+    };
+    let keys = if optional {
+        "enter confirm, esc skip, PgUp/PgDn scroll"
+    } else {
+        "enter confirm, PgUp/PgDn scroll"
+    };
+    let lines = vec![
+        Line::from(String::from(question)).style(Style::new().add_modifier(Modifier::BOLD)),
+        answer,
+        Line::from(keys).style(Style::new().fg(Color::DarkGray)),
+    ];
+    frame.render_widget(Paragraph::new(lines).block(Block::bordered()), area);
+}
+
+/// A single line for an event
+fn describe(event: &Event, game: &GameView) -> String {
+    let player = |index: &usize| &game.players[*index].name;
+    let territory = |index: &usize| &game.board.territories[*index].name;
+    match event {
+        Event::Claim {
+            player: index,
+            territory: claimed,
+        } => format!("{} claims {}", player(index), territory(claimed)),
+        Event::Round { round, order } => format!(
+            "Round {round}: {}",
+            order
+                .iter()
+                .map(player)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Event::Turn {
+            turn,
+            player: index,
+        } => format!("Turn {turn}: {}", player(index)),
+        Event::Reinforcement {
+            player: index,
+            armies,
+        } => format!("{} receives {armies} armies", player(index)),
+        Event::Commander {
+            player: index,
+            commander,
+            territory: placed,
+        } => format!(
+            "{} places a {commander} on {}",
+            player(index),
+            territory(placed)
+        ),
+        Event::Attack {
+            from,
+            to,
+            attacker_losses,
+            defender_losses,
+            ..
+        } => format!(
+            "{} attacks {}: -{attacker_losses} / -{defender_losses}",
+            territory(from),
+            territory(to)
+        ),
+        Event::Capture {
+            player: index,
+            territory: captured,
+            ..
+        } => format!("{} captures {}", player(index), territory(captured)),
+        Event::Fortify {
+            player: index,
+            from,
+            to,
+            armies,
+        } => format!(
+            "{} moves {armies} armies from {} to {}",
+            player(index),
+            territory(from),
+            territory(to)
+        ),
+        Event::Modifier {
+            territory: index,
+            modifier,
+        } => format!("{} becomes a {modifier}", territory(index)),
+        Event::Stalemate { stalemate, .. } => format!("Stalemate: {stalemate}"),
+        Event::Defeat { player: index, by } => {
+            format!("{} is defeated by {}", player(index), player(by))
+        }
+        Event::Forfeit {
+            player: index,
+            reason,
+        } => format!("{} {reason} and forfeits", player(index)),
+    }
+}
@@ -0,0 +1,164 @@
+//! The ways a game can end and the ranked result of a game.
+//! A game is always won by defeating all other players,
+//! and by fulfilling a secret mission when playing with secret missions.
+//! The rules add further victory conditions, which are checked at the end of every attack phase.
+//! When the game is stopped at its turn or time limit, a tiebreak decides the ranking.
+//! Stalemates are detected at the end of every round, depending on the rules.
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+
+/// Additional ways to win the game
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum VictoryCondition {
+    /// Occupy at least the given share of the territories at the end of the attack phase.
+    /// Impassable territories can never be occupied and do not count.
+    Territories(f64),
+    /// Occupy at least the given amount of entire continents for a full round,
+    /// from the end of the attack phase until the start of the next turn of the player
+    Continents(u32),
+    /// Occupy the capitals of all players at the end of the attack phase.
+    /// The capital of a player is the first territory it claims.
+    Capitals,
+}
+
+/// Ranks the players that are not defeated when the game is stopped at its limit.
+/// Ties are broken by territories, then by armies and then by seat.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Tiebreak {
+    /// The most occupied territories
+    #[default]
+    Territories,
+    /// The most armies on the board and in reserve
+    Armies,
+    /// The most reinforcements at the start of the next turn
+    Reinforcements,
+}
+
+/// A situation in which the game is unlikely to ever end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Stalemate {
+    /// No territory changed hands for the given amount of rounds
+    NoCaptures(u64),
+    /// All players ran out of armies in the box and no attack is possible
+    Exhaustion,
+    /// The same position occurred at the end of the given amount of rounds
+    RepeatedPosition(u32),
+}
+
+/// What happens when a stalemate is detected
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StalemateResponse {
+    /// End the game without a winner
+    Draw,
+    /// End the game and rank the players by the tiebreak of the rules
+    Tiebreak,
+    /// Only record the stalemate in the history and keep playing
+    #[default]
+    Continue,
+}
+
+/// Which stalemates are detected and how the game responds to them
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StalemateRules {
+    /// Detect the given amount of rounds without any captured territory
+    pub rounds_without_capture: Option<u64>,
+    /// Detect when all players ran out of armies and no attack is possible
+    pub exhaustion: bool,
+    /// Detect the same position at the end of the given amount of rounds.
+    /// A position consists of the owners and armies of all territories.
+    pub repetitions: Option<u32>,
+    pub response: StalemateResponse,
+}
+
+/// How the game ended
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum Victory {
+    /// A player has defeated all other players
+    Domination,
+    /// A player has fulfilled its secret mission
+    Mission,
+    /// A player has fulfilled a victory condition of the rules
+    Condition(VictoryCondition),
+    /// The game was stopped at its turn or time limit
+    Tiebreak(Tiebreak),
+    /// The game ended in a draw because of a stalemate
+    Draw(Stalemate),
+    /// The game was stopped because of a stalemate and ranked by the tiebreak
+    Stalemate(Stalemate, Tiebreak),
+}
+
+/// The ranked result of a game
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameResult {
+    /// The indices of all players, from first to last.
+    /// Defeated players are ranked by the order in which they were defeated, last defeated first.
+    pub ranking: Vec<usize>,
+    pub victory: Victory,
+    /// The indices of the players in the order in which they claimed their territories,
+    /// as decided by `Rules::turn_order`. The order of every round is recorded in `Event::Round`.
+    pub turn_order: Vec<usize>,
+}
+
+impl GameResult {
+    /// The player that won the game, or ranked first in the tiebreak
+    pub fn first(&self) -> usize {
+        self.ranking[0]
+    }
+
+    /// Whether the game ended without a winner
+    pub fn is_draw(&self) -> bool {
+        matches!(self.victory, Victory::Draw(_))
+    }
+}
+
+impl Display for VictoryCondition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VictoryCondition::Territories(share) => {
+                write!(f, "{:.0}% of the territories", share * 100.0)
+            }
+            VictoryCondition::Continents(continents) => {
+                write!(f, "{continents} continents for a full round")
+            }
+            VictoryCondition::Capitals => write!(f, "all capitals"),
+        }
+    }
+}
+
+impl Display for Tiebreak {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tiebreak::Territories => write!(f, "most territories"),
+            Tiebreak::Armies => write!(f, "most armies"),
+            Tiebreak::Reinforcements => write!(f, "most reinforcements"),
+        }
+    }
+}
+
+impl Display for Stalemate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stalemate::NoCaptures(rounds) => write!(f, "no captures for {rounds} rounds"),
+            Stalemate::Exhaustion => write!(f, "exhaustion"),
+            Stalemate::RepeatedPosition(repetitions) => {
+                write!(f, "the same position {repetitions} times")
+            }
+        }
+    }
+}
+
+impl Display for Victory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Victory::Domination => write!(f, "victory by domination"),
+            Victory::Mission => write!(f, "victory by mission"),
+            Victory::Condition(condition) => write!(f, "victory by {condition}"),
+            Victory::Tiebreak(tiebreak) => write!(f, "tiebreak by {tiebreak}"),
+            Victory::Draw(stalemate) => write!(f, "draw by {stalemate}"),
+            Victory::Stalemate(stalemate, tiebreak) => {
+                write!(f, "stalemate by {stalemate}, tiebreak by {tiebreak}")
+            }
+        }
+    }
+}
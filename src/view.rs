@@ -0,0 +1,331 @@
+//! Read-only views on the game as they are handed to the players.
+//! A view is a snapshot of the game from the perspective of a single player.
+//! When playing with fog of war, the engine hides the information the player is not entitled to.
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::boards::BoardStruct;
+use crate::commanders::Commander;
+use crate::history::Event;
+use crate::missions::Mission;
+use crate::modifiers::Modifier;
+use crate::players::{Player, Seat};
+use crate::rules::FogOfWar;
+use crate::territory::ConnectionType;
+
+/// How much a player can see of a territory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Visibility {
+    /// Both the owner and the armies on the territory are visible
+    Full,
+    /// Only the owner of the territory is visible
+    Owner,
+    /// Nothing is known about the territory, besides its place on the board
+    Hidden,
+}
+
+/// A snapshot of a single territory
+#[derive(Debug, Clone, Serialize)]
+pub struct TerritoryView {
+    pub index: usize,
+    pub name: String,
+    /// The index of the continent the territory belongs to
+    pub continent: usize,
+    /// The indices of the adjacent territories
+    pub connections: Vec<usize>,
+    /// The types of the connections, in the same order as `connections`
+    pub connection_types: Vec<ConnectionType>,
+    pub visibility: Visibility,
+    /// The index of the occupying player.
+    /// `None` if the territory is not yet claimed, neutral or the owner is hidden.
+    pub owner: Option<usize>,
+    /// Whether the territory is neutral and can never be attacked, always visible
+    pub impassable: bool,
+    /// The armies on the territory. `None` if the armies are hidden.
+    pub armies: Option<u32>,
+    /// The commanders on the territory. `None` if the armies are hidden.
+    pub commanders: Option<Vec<Commander>>,
+    /// The modifiers of the territory, always visible
+    pub modifiers: Vec<Modifier>,
+}
+
+/// A snapshot of a single continent
+#[derive(Debug, Clone, Serialize)]
+pub struct ContinentView {
+    pub index: usize,
+    pub name: String,
+    /// Armies rewarded for occupying the entire continent
+    pub armies_reward: u32,
+    /// The indices of the territories the continent contains
+    pub territories: Vec<usize>,
+}
+
+/// A snapshot of the board from the perspective of a single player
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardView {
+    /// The index of the player this view belongs to
+    pub player: usize,
+    pub continents: Vec<ContinentView>,
+    pub territories: Vec<TerritoryView>,
+    /// All territory IDs that are not yet claimed by a player
+    pub free_territories: Vec<usize>,
+}
+
+impl BoardView {
+    /// Creates the view of the given player on the board.
+    /// With fog of war a player only sees the exact armies on its own territories
+    /// and the territories adjacent to them.
+    pub fn new(board: &BoardStruct, player: usize, fog: FogOfWar) -> BoardView {
+        let owners: Vec<Option<usize>> = board
+            .territories
+            .iter()
+            .map(|territory| {
+                territory
+                    .get_player()
+                    .map(|player| player.get_state().index())
+            })
+            .collect();
+
+        let mut visible: Vec<bool> = owners
+            .iter()
+            .map(|owner| fog == FogOfWar::Disabled || *owner == Some(player))
+            .collect();
+        if fog != FogOfWar::Disabled {
+            for territory in &board.territories {
+                if owners[*territory.index.borrow()] == Some(player) {
+                    for adjacent in &*territory.connections.borrow() {
+                        if let Some(adjacent) = adjacent.upgrade() {
+                            visible[*adjacent.index.borrow()] = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        let territories = board
+            .territories
+            .iter()
+            .map(|territory| {
+                let index = *territory.index.borrow();
+                let visibility = if visible[index] {
+                    Visibility::Full
+                } else if fog == FogOfWar::Owners {
+                    Visibility::Owner
+                } else {
+                    Visibility::Hidden
+                };
+
+                let (connections, connection_types) = territory
+                    .connections
+                    .borrow()
+                    .iter()
+                    .filter_map(|adjacent| adjacent.upgrade())
+                    .map(|adjacent| {
+                        (
+                            *adjacent.index.borrow(),
+                            territory.connection_to(&adjacent).unwrap_or_default(),
+                        )
+                    })
+                    .unzip();
+
+                TerritoryView {
+                    index,
+                    name: String::from(&territory.name),
+                    continent: *territory.continent.index.borrow(),
+                    connections,
+                    connection_types,
+                    visibility,
+                    owner: match visibility {
+                        Visibility::Hidden => None,
+                        _ => owners[index],
+                    },
+                    impassable: territory.is_impassable(),
+                    armies: match visibility {
+                        Visibility::Full => Some(*territory.armies.borrow()),
+                        _ => None,
+                    },
+                    commanders: match visibility {
+                        Visibility::Full => Some(territory.commanders.borrow().clone()),
+                        _ => None,
+                    },
+                    modifiers: territory.modifiers.borrow().clone(),
+                }
+            })
+            .collect();
+
+        let continents = board
+            .continents
+            .iter()
+            .map(|continent| ContinentView {
+                index: *continent.index.borrow(),
+                name: String::from(&continent.name),
+                armies_reward: *continent.armies_reward.borrow(),
+                territories: board
+                    .territories
+                    .iter()
+                    .filter(|territory| territory.continent == *continent)
+                    .map(|territory| *territory.index.borrow())
+                    .collect(),
+            })
+            .collect();
+
+        BoardView {
+            player,
+            continents,
+            territories,
+            free_territories: board.free_territories.clone(),
+        }
+    }
+}
+
+/// The publicly known state of a player
+#[derive(Debug, Clone, Serialize)]
+pub struct PlayerView {
+    pub index: usize,
+    pub name: String,
+    /// The armies the player has available to place on the board
+    pub reserve: u32,
+    /// The energy the player has available to buy commanders
+    pub energy: u32,
+    pub defeated: bool,
+    /// The capital of the player, if playing for capitals
+    pub capital: Option<usize>,
+}
+
+/// Everything a player gets to know about the game when making a decision.
+/// The view is immutable and does not provide access to the internal state of the game.
+#[derive(Debug, Clone, Serialize)]
+pub struct GameView<'a> {
+    pub board: BoardView,
+    pub players: Vec<PlayerView>,
+    pub turn: u64,
+    /// All events that happened so far, under fog of war only the events the player witnessed
+    #[serde(skip)]
+    pub history: Cow<'a, [Event]>,
+    /// The longest the player may take for the current decision, see `Rules::decision_time`
+    #[serde(skip)]
+    pub decision_time: Option<Duration>,
+    /// The secret mission of the player, the missions of the others remain secret
+    pub mission: Option<Mission>,
+}
+
+impl<'a> GameView<'a> {
+    /// Creates the view of the given player on the game
+    pub fn new(
+        board: &BoardStruct,
+        players: &[Rc<dyn Player>],
+        seats: &[Seat],
+        player: usize,
+        fog: FogOfWar,
+        turn: u64,
+        history: &'a [Event],
+    ) -> GameView<'a> {
+        GameView {
+            board: BoardView::new(board, player, fog),
+            players: players
+                .iter()
+                .zip(seats)
+                .map(|(player, seat)| {
+                    let state = player.get_state();
+                    PlayerView {
+                        index: state.index(),
+                        name: String::from(&state.name),
+                        reserve: *seat.armies.borrow(),
+                        energy: *seat.energy.borrow(),
+                        defeated: *seat.defeated.borrow(),
+                        capital: seat
+                            .get_capital()
+                            .borrow()
+                            .as_ref()
+                            .map(|capital| *capital.index.borrow()),
+                    }
+                })
+                .collect(),
+            turn,
+            history: match fog {
+                FogOfWar::Disabled => Cow::Borrowed(history),
+                _ => Cow::Owned(witnessed(history, player, fog)),
+            },
+            decision_time: None,
+            mission: seats[player].get_mission().borrow().clone(),
+        }
+    }
+
+    /// The index of the player this view belongs to
+    pub fn player(&self) -> usize {
+        self.board.player
+    }
+
+    /// The armies the player has available to place on the board
+    pub fn reserve(&self) -> u32 {
+        self.players[self.board.player].reserve
+    }
+
+    /// The order in which the players take their turns in the current round
+    pub fn turn_order(&self) -> Option<&[usize]> {
+        self.history.iter().rev().find_map(|event| match event {
+            Event::Round { order, .. } => Some(order.as_slice()),
+            _ => None,
+        })
+    }
+
+    /// The territories occupied by the player
+    pub fn territories(&self) -> Vec<&TerritoryView> {
+        self.board
+            .territories
+            .iter()
+            .filter(|territory| territory.owner == Some(self.board.player))
+            .collect()
+    }
+}
+
+/// The events the given player witnessed under fog of war.
+/// Turns, rounds, defeats, forfeits, stalemates and modifiers are public,
+/// all other events only when the player took part in them.
+/// When the owners remain visible, so do claims and captures.
+/// Whether an event is witnessed never changes, so the history of a player only grows.
+fn witnessed(history: &[Event], player: usize, fog: FogOfWar) -> Vec<Event> {
+    // The player that defended the last attack on every territory
+    let mut defenders: HashMap<usize, Option<usize>> = HashMap::new();
+    history
+        .iter()
+        .filter(|event| match event {
+            Event::Claim {
+                player: claimant, ..
+            } => fog == FogOfWar::Owners || *claimant == player,
+            Event::Capture {
+                player: captor,
+                territory,
+                ..
+            } => {
+                let defender = defenders.get(territory).copied().flatten();
+                fog == FogOfWar::Owners || *captor == player || defender == Some(player)
+            }
+            Event::Attack {
+                attacker,
+                defender,
+                to,
+                ..
+            } => {
+                defenders.insert(*to, *defender);
+                *attacker == player || *defender == Some(player)
+            }
+            Event::Reinforcement {
+                player: reinforced, ..
+            } => *reinforced == player,
+            Event::Commander { player: buyer, .. } => *buyer == player,
+            Event::Fortify { player: mover, .. } => *mover == player,
+            Event::Round { .. }
+            | Event::Turn { .. }
+            | Event::Modifier { .. }
+            | Event::Stalemate { .. }
+            | Event::Defeat { .. }
+            | Event::Forfeit { .. } => true,
+        })
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,129 @@
+use colored::Color::{Blue, Red, White};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::Value;
+use tungstenite::Message;
+
+use rist::api::Api;
+use rist::players::{Player, RandomPlayer};
+use rist::Game;
+
+mod common;
+use common::board;
+
+/// Sends a request and returns the status code with the JSON body
+fn request(address: SocketAddr, method: &str, target: &str) -> (u32, Value) {
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(
+        stream,
+        "{method} {target} HTTP/1.1\r\nHost: {address}\r\n\r\n"
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+/// Waits until the status satisfies the given condition
+fn wait_for(address: SocketAddr, condition: impl Fn(&Value) -> bool) -> Value {
+    loop {
+        let (_, status) = request(address, "GET", "/status");
+        if condition(&status) {
+            return status;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// A paused game is followed, stepped and resumed until it ends
+#[test]
+fn test_api() {
+    let api = Api::new("127.0.0.1:0").unwrap();
+    let address = api.address();
+    api.pause();
+
+    let client = thread::spawn(move || {
+        let status = wait_for(address, |status| status["paused"] == true);
+        let moment = (status["turn"].clone(), status["phase"].clone());
+        let (_, board) = request(address, "GET", "/board");
+        assert_eq!(board["territories"].as_array().unwrap().len(), 4);
+        let (_, players) = request(address, "GET", "/players");
+        assert_eq!(players[1]["name"], "Second");
+        let (_, history) = request(address, "GET", "/history");
+        let claims = history.as_array().unwrap().len();
+        assert!(claims >= 4);
+
+        // The game stays paused until it is stepped
+        thread::sleep(Duration::from_millis(50));
+        let (_, status) = request(address, "GET", "/status");
+        assert_eq!((status["turn"].clone(), status["phase"].clone()), moment);
+        let stream = TcpStream::connect(address).unwrap();
+        let (mut socket, _) =
+            tungstenite::client(format!("ws://{address}/events"), stream).unwrap();
+        request(address, "POST", "/step");
+        wait_for(address, |status| {
+            (status["turn"].clone(), status["phase"].clone()) != moment
+        });
+        let (_, history) = request(address, "GET", &format!("/history?since={claims}"));
+        assert!(!history.as_array().unwrap().is_empty());
+
+        assert_eq!(request(address, "GET", "/unknown").0, 404);
+        assert_eq!(request(address, "GET", "/resume").0, 405);
+        request(address, "POST", "/resume");
+
+        // The socket receives the history, then every event as it happens
+        let mut events = vec![];
+        while let Ok(message) = socket.read() {
+            if let Message::Text(text) = message {
+                events.push(serde_json::from_str::<Value>(&text).unwrap());
+            }
+        }
+        events
+    });
+
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(RandomPlayer::new("First", Red, White)),
+        Rc::new(RandomPlayer::new("Second", Blue, White)),
+    ];
+    let mut game = Game::new(players, board());
+    game.setup(false);
+    api.play(&mut game, Some(50));
+    drop(api);
+
+    let events = client.join().unwrap();
+    let history = game.get_history().borrow();
+    assert_eq!(events.len(), history.len());
+    assert_eq!(
+        events,
+        serde_json::to_value(&*history)
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .clone()
+    );
+}
+
+/// Only loopback addresses are served, and only reading the game is allowed from other origins
+#[test]
+fn test_api_restrictions() {
+    assert!(Api::new("0.0.0.0:0").is_err());
+
+    let api = Api::new("127.0.0.1:0").unwrap();
+    let head = |method: &str, target: &str| {
+        let mut stream = TcpStream::connect(api.address()).unwrap();
+        write!(stream, "{method} {target} HTTP/1.1\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        String::from(response.split_once("\r\n\r\n").unwrap().0)
+    };
+    assert!(head("GET", "/status").contains("Access-Control-Allow-Origin: *"));
+    for target in ["/pause", "/step", "/resume"] {
+        assert!(!head("POST", target).contains("Access-Control-Allow-Origin"));
+    }
+}
@@ -48,7 +48,7 @@ mod claim_territory {
     use rist::boards::{BoardStruct, BoardType};
     use rist::continent::Continent;
     use rist::players::random_player::RandomPlayer;
-    use rist::players::Player;
+    use rist::players::{Player, Seat};
     use rist::territory::Territory;
     use std::rc::Rc;
 
@@ -66,13 +66,14 @@ mod claim_territory {
         );
 
         let player: Rc<dyn Player> = Rc::new(RandomPlayer::new("TestPlayer", Magenta, White));
+        let seat = Seat::new();
 
-        *player.get_state().armies.borrow_mut() = 1;
+        *seat.armies.borrow_mut() = 1;
 
-        board.claim_territory(0, Rc::clone(&player), false);
+        board.claim_territory(0, Rc::clone(&player), &seat, false);
 
         assert_eq!(*territory.armies.borrow(), 1);
-        assert_eq!(*player.get_state().armies.borrow(), 0);
+        assert_eq!(*seat.armies.borrow(), 0);
 
         let mut name = String::from("");
         if let Some(player) = territory.get_player() {
@@ -81,16 +82,8 @@ mod claim_territory {
 
         assert_eq!(name, "TestPlayer");
         assert_eq!(territory.continent.territories_per_player.borrow()[0], 1);
-        assert!(player
-            .get_state()
-            .get_territories()
-            .borrow()
-            .contains(&territory));
-        assert!(player
-            .get_state()
-            .get_continents()
-            .borrow()
-            .contains(&continent));
+        assert!(seat.get_territories().borrow().contains(&territory));
+        assert!(seat.get_continents().borrow().contains(&continent));
     }
 
     #[test]
@@ -109,6 +102,134 @@ mod claim_territory {
 
         let player = Rc::new(RandomPlayer::new("TestPlayer", Magenta, White));
 
-        board.claim_territory(0, player, false);
+        board.claim_territory(0, player, &Seat::new(), false);
+    }
+}
+
+/// Tests rendering boards as text
+mod render {
+    use colored::Color::{Magenta, White};
+    use rist::boards::classic_board::ClassicRenderer;
+    use rist::boards::renderer::ListRenderer;
+    use rist::boards::{BoardStruct, BoardType};
+    use rist::continent::Continent;
+    use rist::players::random_player::RandomPlayer;
+    use rist::players::{Player, Seat};
+    use rist::territory::{ConnectionType, Territory};
+    use std::rc::Rc;
+
+    /// Any board is rendered as a list of its territories
+    #[test]
+    fn test_list_renderer() {
+        let continent = Rc::new(Continent::new("TestContinent", 1, 5, 2));
+        let territory1 = Rc::new(Territory::new("TestTerritory1", Rc::clone(&continent)));
+        let territory2 = Rc::new(Territory::new("TestTerritory2", Rc::clone(&continent)));
+        territory1.create_connections(vec![&territory2]);
+        territory2.create_typed_connections(vec![(&territory1, ConnectionType::FortifyOnly)]);
+
+        let mut board = BoardStruct::generate_board(
+            BoardType::Unimplemented,
+            vec![&continent],
+            vec![&territory1, &territory2],
+            2,
+            None,
+        );
+        let player: Rc<dyn Player> = Rc::new(RandomPlayer::new("TestPlayer", Magenta, White));
+        let seat = Seat::new();
+        *seat.armies.borrow_mut() = 1;
+        board.claim_territory(0, Rc::clone(&player), &seat, false);
+        board.set_extra_info(String::from("Some extra info"));
+
+        let text = board.render();
+        assert!(text.starts_with("TestContinent (+5)\n"));
+        assert!(text.contains("TestTerritory1"));
+        assert!(text.contains("TestPlayer"));
+        assert!(text.contains("TestTerritory2      -  free  (fortify only to TestTerritory1)\n"));
+        assert!(!text.contains("land to"));
+        assert!(text.ends_with("\nSome extra info\n"));
+    }
+
+    /// The renderer is chosen by the type of the board and can be replaced
+    #[test]
+    fn test_set_renderer() {
+        let board = BoardStruct::new(BoardType::ClassicBoard, 2, None);
+        assert!(board.render().contains("NORTH AMERICA"));
+
+        board.set_renderer(Box::new(ListRenderer));
+        assert!(board.render().starts_with("North America (+5)\n  Alaska"));
+
+        board.set_renderer(Box::new(ClassicRenderer));
+        assert!(board.render().contains("NORTH AMERICA"));
+    }
+}
+
+/// Tests drawing boards as SVG images
+mod svg {
+    use colored::Color::{Magenta, White};
+    use rist::boards::renderer::Renderer;
+    use rist::boards::svg::{self, SvgRenderer};
+    use rist::boards::{BoardStruct, BoardType};
+    use rist::continent::Continent;
+    use rist::players::random_player::RandomPlayer;
+    use rist::players::{Player, Seat};
+    use rist::territory::Territory;
+    use std::fs;
+    use std::rc::Rc;
+
+    /// The classic board is drawn at the positions of its definition
+    #[test]
+    fn test_classic_board() {
+        let board = BoardStruct::new(BoardType::ClassicBoard, 2, None);
+        let player: Rc<dyn Player> = Rc::new(RandomPlayer::new("TestPlayer", Magenta, White));
+        let seat = Seat::new();
+        *seat.armies.borrow_mut() = 1;
+        board.territories[0].place_armies(Rc::clone(&player), &seat, 1);
+        board.territories[0].set_player(Some(Rc::downgrade(&player)));
+
+        let image = SvgRenderer.render(&board, &[String::from("Turn 1 & more")]);
+        assert!(image.starts_with("<svg "));
+        assert!(image.ends_with("</svg>\n"));
+        assert_eq!(image.matches("<circle ").count(), 42);
+        assert!(image.contains(r##"<circle cx="92.0" cy="112.0" r="16" fill="#bc3fbc""##));
+        assert!(image.contains("<title>Alaska: TestPlayer</title>"));
+        assert!(image.contains("North America +5"));
+        assert!(image.contains("Turn 1 &amp; more"));
+    }
+
+    /// Boards without positions are laid out automatically
+    #[test]
+    fn test_automatic_layout() {
+        let continent1 = Rc::new(Continent::new("TestContinent1", 1, 2, 2));
+        let continent2 = Rc::new(Continent::new("TestContinent2", 1, 2, 1));
+        let territory1 = Rc::new(Territory::new("TestTerritory1", Rc::clone(&continent1)));
+        let territory2 = Rc::new(Territory::new("TestTerritory2", Rc::clone(&continent1)));
+        let territory3 = Rc::new(Territory::new("TestTerritory3", Rc::clone(&continent2)));
+        territory1.create_connections(vec![&territory2, &territory3]);
+        territory2.create_connections(vec![&territory1]);
+        territory3.create_connections(vec![&territory1, &territory2]);
+        let board = BoardStruct::generate_board(
+            BoardType::Unimplemented,
+            vec![&continent1, &continent2],
+            vec![&territory1, &territory2, &territory3],
+            0,
+            None,
+        );
+
+        let path = std::env::temp_dir().join(format!("rist_board_{}.svg", std::process::id()));
+        svg::export(&board, &path).unwrap();
+        let image = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        // Every connection is drawn once, the one-way connection with an arrowhead
+        assert_eq!(image.matches("<line ").count(), 3);
+        assert_eq!(image.matches("<marker id=\"arrow\"").count(), 1);
+        assert_eq!(image.matches("marker-end=\"url(#arrow)\"").count(), 1);
+        let mut positions: Vec<&str> = image
+            .match_indices("<circle cx=")
+            .map(|(start, _)| &image[start..start + 30])
+            .collect();
+        positions.dedup();
+        assert_eq!(positions.len(), 3);
+        assert!(image.contains("TestContinent2 +2"));
     }
 }
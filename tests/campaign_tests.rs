@@ -0,0 +1,72 @@
+use std::rc::Rc;
+
+use rist::boards::{BoardStruct, BoardType};
+use rist::campaign::Campaign;
+use rist::continent::Continent;
+use rist::modifiers::Modifier;
+use rist::territory::Territory;
+
+/// A campaign is written and parsed in the same format
+#[test]
+fn test_campaign_file() {
+    let mut campaign = Campaign::new();
+    campaign.games = vec![Some(String::from("Player 1")), None];
+    campaign
+        .titles
+        .insert(3, String::from("Alberta of Player 1"));
+    campaign.rewards.insert(1, 6);
+    campaign
+        .modifiers
+        .insert(3, vec![Modifier::City(1), Modifier::Scar]);
+    campaign.bonuses.insert(String::from("Player 2"), 2);
+
+    let path = std::env::temp_dir().join("rist_campaign_test.txt");
+    campaign.save(&path).unwrap();
+    assert_eq!(Campaign::load(&path).unwrap(), campaign);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(Campaign::load(&path).unwrap(), Campaign::new());
+    assert!("reward x 1".parse::<Campaign>().is_err());
+}
+
+/// The marks of a campaign are left on the board
+#[test]
+fn test_campaign_apply() {
+    let continent = Rc::new(Continent::new("TestContinent", 2, 2, 1));
+    let territory = Rc::new(Territory::new("TestTerritory", Rc::clone(&continent)));
+    let board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        vec![&territory],
+        0,
+        None,
+    );
+
+    let mut campaign = Campaign::new();
+    campaign.titles.insert(0, String::from("Capital"));
+    campaign.rewards.insert(0, 4);
+    campaign.modifiers.insert(0, vec![Modifier::Fortress]);
+    campaign.apply(&board).unwrap();
+
+    assert_eq!(territory.title.borrow().as_deref(), Some("Capital"));
+    assert_eq!(*continent.armies_reward.borrow(), 4);
+    assert_eq!(*territory.modifiers.borrow(), vec![Modifier::Fortress]);
+
+    // A campaign of a larger board leaves no marks
+    let board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        vec![&territory],
+        0,
+        None,
+    );
+    let mut campaign = Campaign::new();
+    campaign.titles.insert(0, String::from("Metropolis"));
+    campaign.modifiers.insert(1, vec![Modifier::Scar]);
+    assert!(campaign.apply(&board).is_err());
+    let mut campaign = Campaign::new();
+    campaign.rewards.insert(1, 5);
+    assert!(campaign.apply(&board).is_err());
+    assert_eq!(territory.title.borrow().as_deref(), Some("Capital"));
+    assert_eq!(*continent.armies_reward.borrow(), 4);
+}
@@ -0,0 +1,34 @@
+//! Fixtures shared between the integration tests
+use std::rc::Rc;
+
+use rist::boards::{BoardStruct, BoardType};
+use rist::continent::Continent;
+use rist::territory::Territory;
+
+/// Four connected territories in a single continent
+pub fn board() -> BoardStruct {
+    let continent = Rc::new(Continent::new("TestContinent", 2, 2, 4));
+    let territories: Vec<Rc<Territory>> = (1..=4)
+        .map(|i| {
+            Rc::new(Territory::new(
+                &format!("TestTerritory{i}"),
+                Rc::clone(&continent),
+            ))
+        })
+        .collect();
+    for territory in &territories {
+        territory.create_connections(
+            territories
+                .iter()
+                .filter(|other| other.name != territory.name)
+                .collect(),
+        );
+    }
+    BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        territories.iter().collect(),
+        0,
+        None,
+    )
+}
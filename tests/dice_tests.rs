@@ -2,6 +2,7 @@ use colored::Color::{Magenta, White};
 use std::rc::Rc;
 
 use rist::dice;
+use rist::dice::{BalancedDice, DiceSource, ExpectedDice, ScriptedDice};
 use rist::players::random_player::RandomPlayer;
 use rist::players::Player;
 
@@ -32,3 +33,125 @@ fn test_players_roll_die() {
         assert!((1..=6).contains(&roll));
     }
 }
+
+/// The outcomes of a roll are compared to the well known Risk probabilities
+#[test]
+fn test_roll_outcomes() {
+    let outcomes = dice::roll_outcomes(3, 2);
+    assert_eq!(outcomes.len(), 3);
+    for (attacker_losses, defender_losses, probability) in outcomes {
+        let expected = match (attacker_losses, defender_losses) {
+            (0, 2) => 2890.0 / 7776.0,
+            (1, 1) => 2611.0 / 7776.0,
+            (2, 0) => 2275.0 / 7776.0,
+            _ => panic!("Impossible outcome"),
+        };
+        assert!((probability - expected).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_capture_probability() {
+    assert_eq!(dice::capture_probability(0, 1), 0.0);
+    assert_eq!(dice::capture_probability(1, 0), 1.0);
+    assert!((dice::capture_probability(1, 1) - 15.0 / 36.0).abs() < 1e-9);
+
+    // More attackers means better odds
+    let mut previous = 0.0;
+    for attackers in 1..20 {
+        let probability = dice::capture_probability(attackers, 5);
+        assert!(probability > previous);
+        previous = probability;
+    }
+}
+
+/// The highest dice are compared and the defender wins ties
+#[test]
+fn test_compare_rolls() {
+    assert_eq!(dice::compare_rolls(&[6, 1, 1], &[5, 6]), (2, 0));
+    assert_eq!(dice::compare_rolls(&[6, 4, 1], &[5, 4]), (1, 1));
+    assert_eq!(dice::compare_rolls(&[3, 3], &[3]), (1, 0));
+    assert_eq!(dice::compare_rolls(&[6, 5], &[4, 4]), (0, 2));
+}
+
+#[test]
+fn test_scripted_dice() {
+    let dice = ScriptedDice::new(vec![vec![6, 5, 4], vec![1, 2]]);
+    let attacker_rolls = dice.attacker_rolls(&[6, 6, 6]);
+    assert_eq!(attacker_rolls, vec![6, 5, 4]);
+    assert_eq!(dice.defender_rolls(&attacker_rolls, &[6, 6]), vec![1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "Ran out of scripted rolls.")]
+fn test_scripted_dice_exhausted() {
+    let dice = ScriptedDice::new(vec![]);
+    dice.attacker_rolls(&[6]);
+}
+
+/// Over many battles the expected dice lose exactly the expected amount of armies
+#[test]
+fn test_expected_dice() {
+    let dice = ExpectedDice::default();
+    let mut defender_losses = 0;
+    for _ in 0..1000 {
+        let attacker_rolls = dice.attacker_rolls(&[6, 6, 6]);
+        let defender_rolls = dice.defender_rolls(&attacker_rolls, &[6, 6]);
+        defender_losses += dice::compare_rolls(&attacker_rolls, &defender_rolls).1;
+    }
+    // 3 against 2 dice, the defender loses 1.08 armies per roll on average
+    let expected = 1000.0 * (2.0 * 2890.0 + 2611.0) / 7776.0;
+    assert!((defender_losses as f64 - expected).abs() <= 1.0);
+}
+
+/// Balanced dice stay much closer to the expected losses than the worst case
+#[test]
+fn test_balanced_dice() {
+    let dice = BalancedDice::default();
+    let mut defender_losses = 0;
+    for _ in 0..1000 {
+        let attacker_rolls = dice.attacker_rolls(&[6]);
+        assert_eq!(attacker_rolls.len(), 1);
+        let defender_rolls = dice.defender_rolls(&attacker_rolls, &[6]);
+        assert!((1..=6).contains(&defender_rolls[0]));
+        defender_losses += dice::compare_rolls(&attacker_rolls, &defender_rolls).1;
+    }
+    // 1 against 1 die, the defender loses 15 out of 36 rolls
+    let expected = 1000.0 * 15.0 / 36.0;
+    assert!((defender_losses as f64 - expected).abs() < 20.0);
+}
+
+/// Balanced dice balance towards the expected losses of the dice that are actually rolled
+#[test]
+fn test_balanced_dice_with_eight_sides() {
+    let dice = BalancedDice::new(1.0);
+    let mut defender_losses = 0;
+    for _ in 0..1000 {
+        let attacker_rolls = dice.attacker_rolls(&[8, 8]);
+        let defender_rolls = dice.defender_rolls(&attacker_rolls, &[6]);
+        assert!((1..=6).contains(&defender_rolls[0]));
+        defender_losses += dice::compare_rolls(&attacker_rolls, &defender_rolls).1;
+    }
+    let expected: f64 = dice::dice_outcomes(&[8, 8], &[6])
+        .iter()
+        .map(|(_, defender_losses, p)| *defender_losses as f64 * p)
+        .sum();
+    assert!((defender_losses as f64 - 1000.0 * expected).abs() < 20.0);
+}
+
+/// An 8-sided die improves the odds of the attacker
+#[test]
+fn test_dice_outcomes_with_eight_sides() {
+    let defender_losses = |sides: &[u32]| -> f64 {
+        dice::dice_outcomes(sides, &[6])
+            .iter()
+            .map(|(_, defender_losses, p)| *defender_losses as f64 * p)
+            .sum()
+    };
+    assert!((defender_losses(&[6]) - 15.0 / 36.0).abs() < 1e-9);
+    assert!((defender_losses(&[8]) - 27.0 / 48.0).abs() < 1e-9);
+
+    for roll in dice::roll_sides(&[8, 8, 6]) {
+        assert!((1..=8).contains(&roll));
+    }
+}
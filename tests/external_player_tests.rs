@@ -0,0 +1,168 @@
+use colored::Color::{Blue, Magenta, White};
+use std::fs;
+use std::process::Command;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use rist::history::Event;
+use rist::panics::{Decision, PanicFallback};
+use rist::players::{ExternalPlayer, Player, RandomPlayer};
+use rist::rules::{ForfeitReason, Rules};
+use rist::Game;
+
+mod common;
+use common::board;
+
+/// A bot that claims the first free territory, never places or attacks
+/// and logs the message at the end of the game to the given file
+const PASSIVE_BOT: &str = r#"
+while read -r line; do
+    case "$line" in
+        '{"type":"claim"'*) echo 0 ;;
+        '{"type":"place"'* | '{"type":"commanders"'*) echo '[]' ;;
+        '{"type":"attack"'* | '{"type":"fortify"'*) echo null ;;
+        '{"type":"defend"'*) echo 1 ;;
+        '{"type":"defend_blitz"'*) echo '"MaxDice"' ;;
+        '{"type":"surrender"'*) echo false ;;
+        '{"type":"game_over"'*) echo "$line" > "$1" ;;
+    esac
+done
+"#;
+
+fn bot(script: &str, argument: &str) -> ExternalPlayer {
+    let mut command = Command::new("sh");
+    command.args(["-c", script, "bot", argument]);
+    ExternalPlayer::new(
+        "ExternalPlayer",
+        Magenta,
+        White,
+        command,
+        Duration::from_millis(500),
+    )
+    .unwrap()
+}
+
+fn play(external: ExternalPlayer, rules: Rules) -> Game {
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(external),
+        Rc::new(RandomPlayer::new("RandomPlayer", Blue, White)),
+    ];
+    let mut game = Game::with_rules(players, board(), rules);
+    game.setup(false);
+    game.play(None, Some(200), false, false);
+    game
+}
+
+/// A bot plays an entire game and is notified of the result
+#[test]
+fn test_external_player() {
+    let log = std::env::temp_dir().join(format!("rist_bot_{}.json", std::process::id()));
+    let game = play(bot(PASSIVE_BOT, log.to_str().unwrap()), Rules::default());
+
+    assert!(game.is_over());
+    assert!(game.get_panics().borrow().is_empty());
+    assert!(game
+        .get_history()
+        .borrow()
+        .iter()
+        .any(|event| matches!(event, Event::Claim { player: 0, .. })));
+
+    // The bot exits once the game is dropped
+    drop(game);
+    let message = fs::read_to_string(&log).unwrap();
+    fs::remove_file(&log).unwrap();
+    assert!(message.contains(r#""result":{"ranking":["#));
+}
+
+/// Bots that do not reply in time or reply with invalid JSON panic
+#[test]
+fn test_faulty_bots() {
+    let rules = Rules {
+        on_panic: PanicFallback::Forfeit,
+        ..Rules::default()
+    };
+    let game = play(bot("sleep 5", ""), rules.clone());
+    let panics = game.get_panics().borrow();
+    assert_eq!(panics[0].decision, Decision::Claim);
+    assert!(panics[0].message.contains("did not reply to claim"));
+    assert!(game.get_history().borrow().contains(&Event::Forfeit {
+        player: 0,
+        reason: ForfeitReason::Panic
+    }));
+    assert_eq!(game.get_winner(), Some(1));
+
+    let game = play(bot("while read -r line; do echo ok; done", ""), rules);
+    let panics = game.get_panics().borrow();
+    assert_eq!(panics[0].decision, Decision::Claim);
+    assert!(panics[0].message.contains("invalid JSON"));
+    assert_eq!(game.get_winner(), Some(1));
+}
+
+/// A bot that is slow to place its armies once, and replies to every message after that
+const SLOW_BOT: &str = r#"
+slow=1
+while read -r line; do
+    case "$line" in
+        '{"type":"claim"'*) echo 0 ;;
+        '{"type":"place"'*)
+            if [ $slow = 1 ]; then
+                slow=0
+                sleep 0.7
+            fi
+            echo '[]' ;;
+        '{"type":"commanders"'*) echo '[]' ;;
+        '{"type":"attack"'* | '{"type":"fortify"'*) echo null ;;
+        '{"type":"defend"'*) echo 1 ;;
+        '{"type":"defend_blitz"'*) echo '"MaxDice"' ;;
+        '{"type":"surrender"'*) echo false ;;
+    esac
+done
+"#;
+
+/// A reply that arrives after its timeout is not taken as the reply to the next message
+#[test]
+fn test_late_replies() {
+    let rules = Rules {
+        on_panic: PanicFallback::Default,
+        ..Rules::default()
+    };
+    let game = play(bot(SLOW_BOT, ""), rules);
+
+    // The late placement would be an invalid attack
+    let panics = game.get_panics().borrow();
+    assert_eq!(panics.len(), 1);
+    assert_eq!(panics[0].decision, Decision::Placement);
+    assert!(panics[0].message.contains("did not reply to place"));
+}
+
+/// The game stops waiting for a bot once its decision time is up
+#[test]
+fn test_decision_time() {
+    let mut command = Command::new("sh");
+    command.args(["-c", "sleep 5"]);
+    let external = ExternalPlayer::new(
+        "ExternalPlayer",
+        Magenta,
+        White,
+        command,
+        Duration::from_secs(60),
+    )
+    .unwrap();
+    let rules = Rules {
+        decision_time: Some(Duration::from_millis(100)),
+        ..Rules::default()
+    };
+
+    let start = Instant::now();
+    let game = play(external, rules);
+    assert!(start.elapsed() < Duration::from_secs(5));
+    let panics = game.get_panics().borrow();
+    assert_eq!(panics[0].decision, Decision::Claim);
+    assert!(panics[0].message.contains("within 100ms"));
+    assert_eq!(panics[0].fallback, PanicFallback::Forfeit);
+    assert!(game.get_history().borrow().contains(&Event::Forfeit {
+        player: 0,
+        reason: ForfeitReason::DecisionTime
+    }));
+    assert_eq!(game.get_winner(), Some(1));
+}
@@ -0,0 +1,1544 @@
+use colored::Color::{Blue, Magenta, White};
+use std::cell::RefCell;
+use std::cmp::min;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+
+use rist::boards::{BoardStruct, BoardType};
+use rist::campaign::Campaign;
+use rist::commanders::Commander;
+use rist::continent::Continent;
+use rist::dice::{self, ExpectedDice, ScriptedDice};
+use rist::history::Event;
+use rist::missions::Mission;
+use rist::modifiers::Modifier;
+use rist::panics::{Decision, PanicFallback};
+use rist::players::{Player, PlayerState, PlayerType};
+use rist::rules::{FogOfWar, Forfeit, ForfeitReason, Handicap, Rules, TurnOrder};
+use rist::territory::{ConnectionType, Neutral, Territory};
+use rist::victory::{
+    Stalemate, StalemateResponse, StalemateRules, Tiebreak, Victory, VictoryCondition,
+};
+use rist::view::{BoardView, GameView, Visibility};
+use rist::{Attack, Blitz, DefensePolicy, Game, Phase};
+
+/// A predictable player that piles all armies on its first territory
+/// and attacks with as many dice as possible
+struct TestPlayer {
+    state: PlayerState,
+    /// The rolls of the attacker seen when defending
+    seen_rolls: RefCell<Vec<Option<Vec<u32>>>>,
+    /// Whether the player surrenders at the start of its turn
+    surrenders: RefCell<bool>,
+    /// How long the player thinks about every attack
+    delay: RefCell<Duration>,
+    /// Whether the player panics when asked to attack
+    panics: RefCell<bool>,
+    /// Whether the player answers with decisions it is not allowed to make
+    cheats: RefCell<bool>,
+    /// The boards seen when attacking
+    seen_boards: RefCell<Vec<BoardView>>,
+    /// The missions seen when placing armies
+    seen_missions: RefCell<Vec<Option<Mission>>>,
+    /// How the player blitzes its attacks, if at all
+    blitz: RefCell<Option<Blitz>>,
+    /// How many dice the player claims to roll when defending a single roll, instead of the maximum
+    defense: RefCell<Option<u32>>,
+    /// How the player defends against a blitz
+    blitz_defense: RefCell<DefensePolicy>,
+    /// The armies the player moves at the end of its next turn
+    fortification: RefCell<Option<(usize, usize, u32)>>,
+}
+
+impl TestPlayer {
+    fn new(name: &str, background: colored::Color) -> Self {
+        TestPlayer {
+            state: PlayerState::new(PlayerType::Unimplemented, name, background, White),
+            seen_rolls: RefCell::from(vec![]),
+            surrenders: RefCell::from(false),
+            delay: RefCell::from(Duration::ZERO),
+            panics: RefCell::from(false),
+            cheats: RefCell::from(false),
+            seen_boards: RefCell::from(vec![]),
+            seen_missions: RefCell::from(vec![]),
+            blitz: RefCell::from(None),
+            defense: RefCell::from(None),
+            blitz_defense: RefCell::from(DefensePolicy::MaxDice),
+            fortification: RefCell::from(None),
+        }
+    }
+}
+
+impl Player for TestPlayer {
+    fn get_state(&self) -> &PlayerState {
+        &self.state
+    }
+
+    fn claim_territory(&self, game: &GameView) -> usize {
+        if *self.cheats.borrow() {
+            return game.board.territories.len();
+        }
+        0
+    }
+
+    fn place_armies(&self, game: &GameView) -> Vec<(usize, u32)> {
+        self.seen_missions.borrow_mut().push(game.mission.clone());
+        if *self.cheats.borrow() {
+            return vec![(game.board.territories.len(), game.reserve() + 1)];
+        }
+        match game.territories().first() {
+            Some(territory) if game.reserve() > 0 => vec![(territory.index, game.reserve())],
+            _ => vec![],
+        }
+    }
+
+    /// Buys a land commander for the first territory
+    fn buy_commanders(&self, game: &GameView) -> Vec<(Commander, usize)> {
+        let territories = game.territories();
+        let owned = territories
+            .iter()
+            .any(|territory| !territory.commanders.as_ref().unwrap().is_empty());
+        if game.players[game.player()].energy >= Commander::Land.cost() && !owned {
+            return vec![(Commander::Land, territories[0].index)];
+        }
+        vec![]
+    }
+
+    fn attack(&self, game: &GameView) -> Option<Attack> {
+        thread::sleep(*self.delay.borrow());
+        assert!(!*self.panics.borrow(), "TestPlayer crashed");
+        self.seen_boards.borrow_mut().push(game.board.clone());
+        if *self.cheats.borrow() {
+            // Attacks from the territory of the other player
+            let theirs = game
+                .board
+                .territories
+                .iter()
+                .find(|territory| territory.owner != Some(game.player()))?;
+            return Some(Attack {
+                dice: 1,
+                attacker: theirs.index,
+                defender: theirs.connections[0],
+                blitz: None,
+                roll: None,
+            });
+        }
+        for territory in game.territories() {
+            let armies = territory.armies.unwrap();
+            if armies < 2 {
+                continue;
+            }
+            for (adjacent, connection) in territory
+                .connections
+                .iter()
+                .zip(&territory.connection_types)
+            {
+                let adjacent_territory = &game.board.territories[*adjacent];
+                if adjacent_territory.owner != Some(game.player())
+                    && !adjacent_territory.impassable
+                    && connection.allows_attack()
+                {
+                    return Some(Attack {
+                        dice: (armies - 1).min(connection.max_attack_dice()),
+                        attacker: territory.index,
+                        defender: *adjacent,
+                        blitz: *self.blitz.borrow(),
+                        roll: None,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn capture(&self, _game: &GameView, attack: &Attack) -> u32 {
+        attack.dice
+    }
+
+    fn defend(&self, game: &GameView, attack: &Attack) -> u32 {
+        self.seen_rolls.borrow_mut().push(attack.roll.clone());
+        self.defense.borrow().unwrap_or_else(|| {
+            game.board.territories[attack.defender]
+                .armies
+                .unwrap()
+                .min(2)
+        })
+    }
+
+    fn defend_blitz(&self, _game: &GameView, _attack: &Attack) -> DefensePolicy {
+        *self.blitz_defense.borrow()
+    }
+
+    fn fortify(&self, _game: &GameView) -> Option<(usize, usize, u32)> {
+        self.fortification.borrow_mut().take()
+    }
+
+    fn surrender(&self, _game: &GameView) -> bool {
+        *self.surrenders.borrow()
+    }
+}
+
+/// Two territories in a single continent
+fn board() -> BoardStruct {
+    let continent = Rc::new(Continent::new("TestContinent", 2, 2, 2));
+    let territory1 = Rc::new(Territory::new("TestTerritory1", Rc::clone(&continent)));
+    let territory2 = Rc::new(Territory::new("TestTerritory2", Rc::clone(&continent)));
+    territory1.create_connections(vec![&territory2]);
+    territory2.create_connections(vec![&territory1]);
+
+    BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        vec![&territory1, &territory2],
+        0,
+        None,
+    )
+}
+
+fn play(rules: Rules) -> (Rc<TestPlayer>, Rc<TestPlayer>, Game) {
+    play_on(board(), rules)
+}
+
+fn play_on(board: BoardStruct, rules: Rules) -> (Rc<TestPlayer>, Rc<TestPlayer>, Game) {
+    let player1 = Rc::new(TestPlayer::new("TestPlayer1", Magenta));
+    let player2 = Rc::new(TestPlayer::new("TestPlayer2", Blue));
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::clone(&player1) as Rc<dyn Player>,
+        Rc::clone(&player2) as Rc<dyn Player>,
+    ];
+
+    let mut game = Game::with_rules(players, board, rules);
+    game.setup(false);
+    game.play(None, Some(100), false, false);
+    (player1, player2, game)
+}
+
+/// The defender only sees the roll of the attacker when the rules allow it
+#[test]
+fn test_defender_sees_roll() {
+    let (player1, player2, _) = play(Rules::default());
+    for player in [&player1, &player2] {
+        assert!(player.seen_rolls.borrow().iter().all(|roll| roll.is_none()));
+    }
+
+    let rules = Rules {
+        defender_sees_roll: true,
+        ..Rules::default()
+    };
+    let (player1, player2, _) = play(rules);
+    let mut defended = 0;
+    for player in [&player1, &player2] {
+        for roll in player.seen_rolls.borrow().iter() {
+            let roll = roll.as_ref().unwrap();
+            assert!(!roll.is_empty() && roll.len() <= 3);
+            defended += 1;
+        }
+    }
+    assert!(defended > 0);
+}
+
+/// Expected dice make every game play out the same way
+#[test]
+fn test_expected_dice_game() {
+    let rules = Rules {
+        dice: Rc::new(ExpectedDice::default()),
+        ..Rules::default()
+    };
+    let (_, _, game) = play(rules);
+    assert!(game.get_winner().is_some());
+}
+
+/// Players buy commanders with their energy when playing with commanders
+#[test]
+fn test_commanders() {
+    let rules = Rules {
+        commanders: true,
+        ..Rules::default()
+    };
+    let (_, _, game) = play(rules);
+    assert!(game.get_history().borrow().iter().any(|event| matches!(
+        event,
+        Event::Commander {
+            commander: Commander::Land,
+            ..
+        }
+    )));
+}
+
+/// Cities add to the reinforcements of their owner
+#[test]
+fn test_city_reinforcements() {
+    let board = board();
+    for territory in &board.territories {
+        territory.modifiers.borrow_mut().push(Modifier::City(2));
+    }
+    let (_, _, game) = play_on(board, Rules::default());
+
+    let reinforcements = game
+        .get_history()
+        .borrow()
+        .iter()
+        .find_map(|event| match event {
+            Event::Reinforcement { armies, .. } => Some(*armies),
+            _ => None,
+        });
+    assert_eq!(reinforcements, Some(5));
+}
+
+/// Every game of a campaign leaves its marks for the next game
+#[test]
+fn test_campaign() {
+    let mut campaign = Campaign::new();
+    for _ in 0..2 {
+        let board = board();
+        campaign.apply(&board).unwrap();
+        let (_, _, game) = play_on(board, Rules::default());
+        campaign.record(&game);
+    }
+
+    assert_eq!(campaign.games.len(), 2);
+    let winners = campaign.games.iter().flatten().count();
+    assert_eq!(campaign.titles.len(), winners);
+    let bonuses: u32 = campaign.bonuses.values().sum();
+    assert_eq!(bonuses as usize, 4 - winners);
+}
+
+/// Neutral territories are never claimed but can be conquered, impassable ones are never attacked
+#[test]
+fn test_neutral_territories() {
+    let continent = Rc::new(Continent::new("TestContinent", 2, 2, 4));
+    let territory1 = Rc::new(Territory::new("TestTerritory1", Rc::clone(&continent)));
+    let territory2 = Rc::new(Territory::new("TestTerritory2", Rc::clone(&continent)));
+    let neutral = Rc::new(Territory::new_neutral(
+        "Neutral",
+        Rc::clone(&continent),
+        Neutral {
+            armies: 3,
+            impassable: false,
+        },
+    ));
+    let impassable = Rc::new(Territory::new_neutral(
+        "Impassable",
+        Rc::clone(&continent),
+        Neutral {
+            armies: 5,
+            impassable: true,
+        },
+    ));
+    territory1.create_connections(vec![&neutral, &impassable]);
+    territory2.create_connections(vec![&neutral, &impassable]);
+    neutral.create_connections(vec![&territory1, &territory2]);
+    impassable.create_connections(vec![&territory1, &territory2]);
+    let board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        vec![&territory1, &territory2, &neutral, &impassable],
+        0,
+        None,
+    );
+    assert_eq!(board.free_territories, vec![0, 1]);
+
+    let (_, _, game) = play_on(board, Rules::default());
+    assert!(game.get_winner().is_some());
+    assert!(game.get_history().borrow().iter().any(|event| matches!(
+        event,
+        Event::Attack {
+            defender: None,
+            to: 2,
+            ..
+        }
+    )));
+    assert!(impassable.is_neutral());
+    assert_eq!(*impassable.armies.borrow(), 5);
+}
+
+/// Territories only attack over connections that allow it
+#[test]
+fn test_typed_connections() {
+    let continent = Rc::new(Continent::new("TestContinent", 2, 2, 2));
+    let territory1 = Rc::new(Territory::new("TestTerritory1", Rc::clone(&continent)));
+    let territory2 = Rc::new(Territory::new("TestTerritory2", Rc::clone(&continent)));
+    territory1.create_typed_connections(vec![(&territory2, ConnectionType::Sea)]);
+    territory2.create_typed_connections(vec![(&territory1, ConnectionType::FortifyOnly)]);
+    let board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        vec![&territory1, &territory2],
+        0,
+        None,
+    );
+
+    let (_, _, game) = play_on(board, Rules::default());
+    let history = game.get_history().borrow();
+    assert!(history
+        .iter()
+        .any(|event| matches!(event, Event::Attack { .. })));
+    assert!(history
+        .iter()
+        .all(|event| !matches!(event, Event::Attack { from: 1, .. })));
+}
+
+/// Four territories in a single continent, connected in a line
+fn line_board() -> BoardStruct {
+    let continent = Rc::new(Continent::new("TestContinent", 2, 2, 4));
+    let territories: Vec<Rc<Territory>> = (1..=4)
+        .map(|i| {
+            Rc::new(Territory::new(
+                &format!("TestTerritory{i}"),
+                Rc::clone(&continent),
+            ))
+        })
+        .collect();
+    for (i, territory) in territories.iter().enumerate() {
+        let connections: Vec<&Rc<Territory>> = territories
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| i.abs_diff(*j) == 1)
+            .map(|(_, territory)| territory)
+            .collect();
+        territory.create_connections(connections);
+    }
+    BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        territories.iter().collect(),
+        0,
+        None,
+    )
+}
+
+/// Plays the first turn up to the fortify phase on two pairs of territories that cannot reach
+/// each other. The first player owns the first pair, connected by the given connection.
+fn fortify_game(connection: ConnectionType) -> (Rc<TestPlayer>, Game) {
+    let continent = Rc::new(Continent::new("TestContinent", 2, 2, 4));
+    let territories: Vec<Rc<Territory>> = (1..=4)
+        .map(|i| {
+            Rc::new(Territory::new(
+                &format!("TestTerritory{i}"),
+                Rc::clone(&continent),
+            ))
+        })
+        .collect();
+    territories[0].create_typed_connections(vec![(&territories[1], connection)]);
+    territories[1].create_typed_connections(vec![(&territories[0], connection)]);
+    territories[2].create_connections(vec![&territories[3]]);
+    territories[3].create_connections(vec![&territories[2]]);
+    let board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        territories.iter().collect(),
+        0,
+        None,
+    );
+
+    let player1 = Rc::new(TestPlayer::new("TestPlayer1", Magenta));
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::clone(&player1) as Rc<dyn Player>,
+        Rc::new(TestPlayer::new("TestPlayer2", Blue)),
+    ];
+    let rules = Rules {
+        handicaps: vec![Handicap {
+            territories: vec![0, 1],
+            ..Handicap::default()
+        }],
+        turn_order: TurnOrder::Fixed,
+        on_panic: PanicFallback::Default,
+        ..Rules::default()
+    };
+    let mut game = Game::with_rules(players, board, rules);
+    game.setup(false);
+    assert!(game.step(false));
+    assert!(game.step(false));
+    assert_eq!(game.get_phase(), Phase::Fortify);
+    (player1, game)
+}
+
+/// Armies are moved between adjacent territories of the player at the end of its turn
+#[test]
+fn test_fortify() {
+    let (player1, mut game) = fortify_game(ConnectionType::Land);
+    let armies = *game.get_board().territories[0].armies.borrow();
+    *player1.fortification.borrow_mut() = Some((0, 1, armies - 1));
+    assert!(game.step(false));
+
+    let territories = &game.get_board().territories;
+    assert_eq!(*territories[0].armies.borrow(), 1);
+    assert_eq!(*territories[1].armies.borrow(), armies);
+    assert!(game.get_history().borrow().contains(&Event::Fortify {
+        player: 0,
+        from: 0,
+        to: 1,
+        armies: armies - 1
+    }));
+}
+
+/// Armies are only moved over connections that allow fortifying
+#[test]
+fn test_fortify_attack_only() {
+    let (player1, mut game) = fortify_game(ConnectionType::AttackOnly);
+    let armies = *game.get_board().territories[0].armies.borrow();
+    *player1.fortification.borrow_mut() = Some((0, 1, 1));
+    assert!(game.step(false));
+
+    assert_eq!(*game.get_board().territories[0].armies.borrow(), armies);
+    let panics = game.get_panics().borrow();
+    assert_eq!(panics.len(), 1);
+    assert_eq!(panics[0].decision, Decision::Fortify);
+    assert!(panics[0].message.contains("attack only"));
+    assert!(!game
+        .get_history()
+        .borrow()
+        .iter()
+        .any(|event| matches!(event, Event::Fortify { .. })));
+}
+
+/// The claim order and the order of every round follow the turn order rules
+#[test]
+fn test_turn_order() {
+    let rules = Rules {
+        turn_order: TurnOrder::Fixed,
+        snake_claim: true,
+        ..Rules::default()
+    };
+    let (_, _, game) = play_on(line_board(), rules);
+    let history = game.get_history().borrow();
+
+    let claims: Vec<usize> = history
+        .iter()
+        .filter_map(|event| match event {
+            Event::Claim { player, .. } => Some(*player),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(claims, vec![0, 1, 1, 0]);
+    assert_eq!(game.get_result().unwrap().turn_order, vec![0, 1]);
+    assert_eq!(
+        history[4],
+        Event::Round {
+            round: 1,
+            order: vec![0, 1]
+        }
+    );
+
+    // Every turn is taken by the next player in the order of the round
+    let mut order = vec![];
+    for event in history.iter() {
+        match event {
+            Event::Round { order: round, .. } => order = round.clone(),
+            Event::Turn { player, .. } => assert_eq!(order.remove(0), *player),
+            _ => {}
+        }
+    }
+}
+
+/// The weakest player goes first every round
+#[test]
+fn test_weakest_first() {
+    let rules = Rules {
+        turn_order: TurnOrder::WeakestFirst,
+        ..Rules::default()
+    };
+    let (_, _, game) = play_on(line_board(), rules);
+
+    // Replay the owners of the territories to know the territories of every player
+    let mut owners = [None; 4];
+    let mut rounds = 0;
+    for event in game.get_history().borrow().iter() {
+        match event {
+            Event::Claim { player, territory }
+            | Event::Capture {
+                player, territory, ..
+            } => owners[*territory] = Some(*player),
+            Event::Round { order, .. } => {
+                let territories = |player: usize| {
+                    owners
+                        .iter()
+                        .filter(|owner| **owner == Some(player))
+                        .count()
+                };
+                assert!(territories(order[0]) <= territories(order[1]));
+                rounds += 1;
+            }
+            _ => {}
+        }
+    }
+    assert!(rounds > 0);
+}
+
+/// Players with as many territories and armies go first in the order of their index
+#[test]
+fn test_weakest_first_ties() {
+    let continent = Rc::new(Continent::new("TestContinent", 3, 2, 3));
+    let territories: Vec<Rc<Territory>> = (1..=3)
+        .map(|i| {
+            Rc::new(Territory::new(
+                &format!("TestTerritory{i}"),
+                Rc::clone(&continent),
+            ))
+        })
+        .collect();
+    let board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        territories.iter().collect(),
+        0,
+        None,
+    );
+
+    // The first player starts with more armies, the others are tied
+    let rules = Rules {
+        turn_order: TurnOrder::WeakestFirst,
+        handicaps: vec![Handicap {
+            starting_armies: 5,
+            ..Handicap::default()
+        }],
+        ..Rules::default()
+    };
+    let players: Vec<Rc<dyn Player>> = (1..=3)
+        .map(|i| Rc::new(TestPlayer::new(&format!("TestPlayer{i}"), Magenta)) as Rc<dyn Player>)
+        .collect();
+    let mut game = Game::with_rules(players, board, rules);
+    game.setup(false);
+    game.play(None, Some(9), false, false);
+
+    // Everyone is tied before the territories are claimed
+    assert_eq!(game.get_result().unwrap().turn_order, vec![0, 1, 2]);
+    let rounds: Vec<Vec<usize>> = game
+        .get_history()
+        .borrow()
+        .iter()
+        .filter_map(|event| match event {
+            Event::Round { order, .. } => Some(order.clone()),
+            _ => None,
+        })
+        .collect();
+    // The extra armies only reach the board during the first round
+    assert_eq!(rounds, vec![vec![0, 1, 2], vec![1, 2, 0], vec![1, 2, 0]]);
+}
+
+/// A handicapped player claims its preassigned territories and receives adjusted reinforcements
+#[test]
+fn test_handicaps() {
+    let rules = Rules {
+        handicaps: vec![Handicap {
+            starting_armies: -10,
+            reinforcement_multiplier: 2.0,
+            reinforcement_bonus: -1,
+            territories: vec![3, 2],
+            ..Handicap::default()
+        }],
+        turn_order: TurnOrder::Fixed,
+        ..Rules::default()
+    };
+    let (_, _, game) = play_on(line_board(), rules);
+    let history = game.get_history().borrow();
+
+    assert_eq!(
+        history[..2],
+        [
+            Event::Claim {
+                player: 0,
+                territory: 3
+            },
+            Event::Claim {
+                player: 0,
+                territory: 2
+            }
+        ]
+    );
+
+    // The handicapped player takes the first turn with 3 territories
+    let reinforcement = history.iter().find_map(|event| match event {
+        Event::Reinforcement { player, armies } => Some((*player, *armies)),
+        _ => None,
+    });
+    assert_eq!(reinforcement, Some((0, 5)));
+    assert_eq!(Handicap::default().reinforcements(3), 3);
+}
+
+/// The game can be advanced one phase or one turn at a time
+#[test]
+fn test_step() {
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(TestPlayer::new("TestPlayer1", Magenta)),
+        Rc::new(TestPlayer::new("TestPlayer2", Blue)),
+    ];
+    let rules = Rules {
+        turn_order: TurnOrder::Fixed,
+        ..Rules::default()
+    };
+    let mut game = Game::with_rules(players, line_board(), rules);
+    game.setup(false);
+    assert_eq!((game.get_round(), game.get_turn()), (0, 0));
+    assert_eq!(game.get_current_player(), None);
+
+    assert!(game.step(false));
+    assert_eq!((game.get_round(), game.get_turn()), (1, 1));
+    assert_eq!(game.get_current_player(), Some(0));
+    assert_eq!(game.get_turn_queue(), vec![1]);
+    assert_eq!(game.get_phase(), Phase::Attack);
+
+    assert!(game.step(false));
+    assert_eq!(game.get_phase(), Phase::Fortify);
+    assert!(game.step(false));
+    assert_eq!(game.get_phase(), Phase::Reinforce);
+    assert_eq!(game.get_current_player(), None);
+
+    while game.step_turn(false) {
+        assert_eq!(game.get_current_player(), None);
+        assert!(game.get_turn() <= 2 * game.get_round());
+    }
+    assert!(game.is_over());
+    assert!(!game.step(false));
+}
+
+/// Defeating all other players wins the game and ranks every player
+#[test]
+fn test_domination_result() {
+    let (_, _, game) = play(Rules::default());
+    let result = game.get_result().unwrap();
+    assert_eq!(result.victory, Victory::Domination);
+    assert_eq!(Some(result.first()), game.get_winner());
+    assert_eq!(result.ranking.len(), 2);
+}
+
+/// Occupying enough territories wins the game
+#[test]
+fn test_territories_victory() {
+    let rules = Rules {
+        victory_conditions: vec![VictoryCondition::Territories(0.75)],
+        turn_order: TurnOrder::Fixed,
+        ..Rules::default()
+    };
+    let (_, _, game) = play_on(line_board(), rules);
+    let result = game.get_result().unwrap();
+    assert_eq!(
+        result.victory,
+        Victory::Condition(VictoryCondition::Territories(0.75))
+    );
+}
+
+/// The share of territories only counts the territories that can be occupied
+#[test]
+fn test_territories_victory_ignores_impassable() {
+    let continent = Rc::new(Continent::new("TestContinent", 2, 2, 4));
+    let territory1 = Rc::new(Territory::new("TestTerritory1", Rc::clone(&continent)));
+    let territory2 = Rc::new(Territory::new("TestTerritory2", Rc::clone(&continent)));
+    let impassables: Vec<Rc<Territory>> = (1..=2)
+        .map(|i| {
+            Rc::new(Territory::new_neutral(
+                &format!("Impassable{i}"),
+                Rc::clone(&continent),
+                Neutral {
+                    armies: 5,
+                    impassable: true,
+                },
+            ))
+        })
+        .collect();
+    for territory in [&territory1, &territory2] {
+        territory.create_connections(impassables.iter().collect());
+    }
+    let board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        vec![&territory1, &territory2, &impassables[0], &impassables[1]],
+        0,
+        None,
+    );
+
+    // The players cannot attack each other and already occupy half of the territories
+    // that can be occupied, which is only a quarter of the board
+    let rules = Rules {
+        victory_conditions: vec![VictoryCondition::Territories(0.5)],
+        turn_order: TurnOrder::Fixed,
+        dice: Rc::new(ExpectedDice::default()),
+        ..Rules::default()
+    };
+    let (_, _, game) = play_on(board, rules);
+    let result = game.get_result().unwrap();
+    assert_eq!(
+        result.victory,
+        Victory::Condition(VictoryCondition::Territories(0.5))
+    );
+    assert_eq!(result.first(), 0);
+    assert_eq!(game.get_turn(), 1);
+}
+
+/// Occupying the capitals of all players wins the game
+#[test]
+fn test_capitals_victory() {
+    let rules = Rules {
+        victory_conditions: vec![VictoryCondition::Capitals],
+        turn_order: TurnOrder::Fixed,
+        dice: Rc::new(ExpectedDice::default()),
+        ..Rules::default()
+    };
+    let (_, _, game) = play_on(line_board(), rules);
+
+    // The capital of every player is the first territory it claimed
+    let history = game.get_history().borrow();
+    for (index, seat) in game.get_seats().iter().enumerate() {
+        let first = history.iter().find_map(|event| match event {
+            Event::Claim { player, territory } if *player == index => Some(*territory),
+            _ => None,
+        });
+        let capital = seat.get_capital().borrow();
+        assert_eq!(
+            capital.as_ref().map(|capital| *capital.index.borrow()),
+            first
+        );
+    }
+
+    let result = game.get_result().unwrap();
+    assert_eq!(
+        result.victory,
+        Victory::Condition(VictoryCondition::Capitals)
+    );
+    let winner = result.first();
+    assert!(game.get_seats().iter().all(|seat| {
+        let capital = seat.get_capital().borrow();
+        capital
+            .as_ref()
+            .unwrap()
+            .get_player()
+            .unwrap()
+            .get_state()
+            .index()
+            == winner
+    }));
+    assert!(game
+        .get_seats()
+        .iter()
+        .any(|seat| !*seat.defeated.borrow() && seat.get_territories().borrow().len() < 4));
+}
+
+/// A game stopped at its turn limit is ranked by the tiebreak
+#[test]
+fn test_tiebreak() {
+    let rules = Rules {
+        turn_order: TurnOrder::Fixed,
+        tiebreak: Tiebreak::Armies,
+        dice: Rc::new(ExpectedDice::default()),
+        ..Rules::default()
+    };
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(TestPlayer::new("TestPlayer1", Magenta)),
+        Rc::new(TestPlayer::new("TestPlayer2", Blue)),
+    ];
+    let mut game = Game::with_rules(players, line_board(), rules);
+    game.setup(false);
+    game.play(None, Some(1), false, false);
+
+    // The first player has placed its reinforcements, the second player has not
+    let result = game.get_result().unwrap();
+    assert_eq!(game.get_turn(), 1);
+    assert_eq!(game.get_winner(), None);
+    assert_eq!(result.victory, Victory::Tiebreak(Tiebreak::Armies));
+    assert_eq!(result.ranking, vec![0, 1]);
+    assert!(game.is_over());
+}
+
+/// Holding enough continents for a full round wins the game
+#[test]
+fn test_continents_victory() {
+    let continents: Vec<Rc<Continent>> = (1..=4)
+        .map(|i| Rc::new(Continent::new(&format!("TestContinent{i}"), 2, 1, 1)))
+        .collect();
+    let territories: Vec<Rc<Territory>> = continents
+        .iter()
+        .enumerate()
+        .map(|(i, continent)| {
+            Rc::new(Territory::new(
+                &format!("TestTerritory{i}"),
+                Rc::clone(continent),
+            ))
+        })
+        .collect();
+    for (i, territory) in territories.iter().enumerate() {
+        let connections: Vec<&Rc<Territory>> = territories
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| i.abs_diff(*j) == 1)
+            .map(|(_, territory)| territory)
+            .collect();
+        territory.create_connections(connections);
+    }
+    let board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        continents.iter().collect(),
+        territories.iter().collect(),
+        0,
+        None,
+    );
+
+    let rules = Rules {
+        victory_conditions: vec![VictoryCondition::Continents(1)],
+        turn_order: TurnOrder::Fixed,
+        dice: Rc::new(ExpectedDice::default()),
+        ..Rules::default()
+    };
+    let (_, _, game) = play_on(board, rules);
+    let result = game.get_result().unwrap();
+    assert_eq!(
+        result.victory,
+        Victory::Condition(VictoryCondition::Continents(1))
+    );
+    assert_eq!(result.first(), 0);
+    assert_eq!(game.get_turn(), 3);
+}
+
+/// Fulfilling a secret mission during an attack ends the game
+#[test]
+fn test_mission_victory() {
+    // The first territory of every player only borders the second territory of the other
+    let continent = Rc::new(Continent::new("TestContinent", 2, 2, 4));
+    let territories: Vec<Rc<Territory>> = (1..=4)
+        .map(|i| {
+            Rc::new(Territory::new(
+                &format!("TestTerritory{i}"),
+                Rc::clone(&continent),
+            ))
+        })
+        .collect();
+    for (territory, adjacent) in [(0, 3), (3, 0), (1, 2), (2, 1)] {
+        territories[territory].create_connections(vec![&territories[adjacent]]);
+    }
+    let board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        territories.iter().collect(),
+        0,
+        None,
+    );
+
+    // A single roll of three sixes against a one takes the second territory of the second player
+    let rules = Rules {
+        dice: Rc::new(ScriptedDice::new(vec![vec![6, 6, 6], vec![1]])),
+        turn_order: TurnOrder::Fixed,
+        secret_missions: true,
+        ..Rules::default()
+    };
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(TestPlayer::new("TestPlayer1", Magenta)),
+        Rc::new(TestPlayer::new("TestPlayer2", Blue)),
+    ];
+    let mut game = Game::with_rules(players, board, rules);
+    game.setup(false);
+    let seats = game.get_seats();
+    assert!(seats
+        .iter()
+        .all(|seat| seat.get_mission().borrow().is_some()));
+    seats[0].set_mission(Some(Mission::OccupyTerritories(3)));
+    seats[1].set_mission(Some(Mission::OccupyTerritories(4)));
+
+    assert!(game.step(false));
+    assert!(!game.step(false));
+    let result = game.get_result().unwrap();
+    assert_eq!(result.ranking, vec![0, 1]);
+    assert_eq!(result.victory, Victory::Mission);
+    assert_eq!(game.get_turn(), 1);
+    assert!(!*game.get_seats()[1].defeated.borrow());
+}
+
+/// Blitzes the three armies on the second territory, whose owner defends with the given policy
+/// and answers with the given amount of dice when asked per roll.
+/// The scripted rolls only match when the defender rolls `dice` dice while it has the armies.
+fn blitz_game(policy: DefensePolicy, defense: Option<u32>, dice: u32) -> Game {
+    let mut rolls = vec![];
+    let mut defenders = 3;
+    while defenders > 0 {
+        let defender_dice = dice.min(defenders);
+        rolls.push(vec![6, 6, 6]);
+        rolls.push(vec![1; defender_dice as usize]);
+        defenders -= defender_dice;
+    }
+    let rules = Rules {
+        dice: Rc::new(ScriptedDice::new(rolls)),
+        turn_order: TurnOrder::Fixed,
+        on_panic: PanicFallback::Default,
+        ..Rules::default()
+    };
+    let attacker = TestPlayer::new("TestPlayer1", Magenta);
+    *attacker.blitz.borrow_mut() = Some(Blitz::UntilCaptured);
+    let defender = TestPlayer::new("TestPlayer2", Blue);
+    *defender.blitz_defense.borrow_mut() = policy;
+    *defender.defense.borrow_mut() = defense;
+    let players: Vec<Rc<dyn Player>> = vec![Rc::new(attacker), Rc::new(defender)];
+
+    let mut game = Game::with_rules(players, board(), rules);
+    game.setup(false);
+    *game.get_board().territories[1].armies.borrow_mut() = 3;
+    assert!(game.step(false));
+    assert!(!game.step(false));
+    game
+}
+
+/// The defender rolls the dice it decided on, invalid decisions fall back to the maximum
+#[test]
+fn test_blitz_defender_dice() {
+    for (policy, defense, dice, invalid) in [
+        (DefensePolicy::MaxDice, None, 2, None),
+        (DefensePolicy::Dice(1), None, 1, None),
+        (DefensePolicy::PerRoll, None, 2, None),
+        (DefensePolicy::PerRoll, Some(1), 1, None),
+        (
+            DefensePolicy::Dice(0),
+            None,
+            2,
+            Some(Decision::BlitzDefense),
+        ),
+        (
+            DefensePolicy::Dice(5),
+            None,
+            2,
+            Some(Decision::BlitzDefense),
+        ),
+        (DefensePolicy::PerRoll, Some(0), 2, Some(Decision::Defense)),
+        (DefensePolicy::PerRoll, Some(5), 2, Some(Decision::Defense)),
+    ] {
+        let game = blitz_game(policy, defense, dice);
+        let result = game.get_result().unwrap();
+        assert_eq!(result.ranking, vec![0, 1], "{policy:?} {defense:?}");
+        let defender_losses: u32 = game
+            .get_history()
+            .borrow()
+            .iter()
+            .filter_map(|event| match event {
+                Event::Attack {
+                    defender_losses, ..
+                } => Some(*defender_losses),
+                _ => None,
+            })
+            .sum();
+        assert_eq!(defender_losses, 3, "{policy:?} {defense:?}");
+        let panics = game.get_panics().borrow();
+        match invalid {
+            Some(decision) => assert!(
+                !panics.is_empty()
+                    && panics
+                        .iter()
+                        .all(|report| report.player == 1 && report.decision == decision),
+                "{policy:?} {defense:?}"
+            ),
+            None => assert!(panics.is_empty(), "{policy:?} {defense:?}"),
+        }
+    }
+}
+
+/// Plays the first attack phase, in which the given armies on the first territory keep blitzing
+/// the given armies on the second territory and lose two armies on every roll.
+/// Returns the armies the attacker lost in every blitz.
+fn losing_blitzes(blitz: Blitz, attackers: u32, defenders: u32) -> Vec<u32> {
+    let mut rolls = vec![];
+    let mut armies = attackers;
+    while armies > 1 {
+        let dice = min(3, armies - 1);
+        rolls.push(vec![1; dice as usize]);
+        rolls.push(vec![6, 6]);
+        armies -= min(dice, 2);
+    }
+    let rules = Rules {
+        dice: Rc::new(ScriptedDice::new(rolls)),
+        turn_order: TurnOrder::Fixed,
+        ..Rules::default()
+    };
+    let attacker = TestPlayer::new("TestPlayer1", Magenta);
+    *attacker.blitz.borrow_mut() = Some(blitz);
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(attacker),
+        Rc::new(TestPlayer::new("TestPlayer2", Blue)),
+    ];
+
+    let mut game = Game::with_rules(players, board(), rules);
+    game.setup(false);
+    assert!(game.step(false));
+    *game.get_board().territories[0].armies.borrow_mut() = attackers;
+    *game.get_board().territories[1].armies.borrow_mut() = defenders;
+    assert!(game.step(false));
+    assert_eq!(*game.get_board().territories[0].armies.borrow(), 1);
+    let history = game.get_history().borrow();
+    history
+        .iter()
+        .filter_map(|event| match event {
+            Event::Attack {
+                attacker_losses, ..
+            } => Some(*attacker_losses),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A blitz stops once the attacker is down to the given armies, but always rolls at least once
+#[test]
+fn test_blitz_until_armies_left() {
+    // 10 -> 8 -> 6 -> 4, then every new blitz rolls once: 4 -> 2 -> 1
+    assert_eq!(
+        losing_blitzes(Blitz::UntilArmiesLeft(5), 10, 10),
+        vec![6, 2, 1]
+    );
+}
+
+/// A blitz stops once the odds of the armies that can attack drop below the given odds,
+/// but always rolls at least once
+#[test]
+fn test_blitz_until_odds() {
+    // 8 armies can attack from 9, the odds of 6 armies attacking from 7 are too low
+    let odds = dice::capture_probability(7, 3);
+    assert!(dice::capture_probability(6, 3) < odds);
+    assert_eq!(
+        losing_blitzes(Blitz::UntilOdds(odds), 9, 3),
+        vec![2, 2, 2, 2]
+    );
+}
+
+/// Every player sees its own secret mission, but not the missions of the others
+#[test]
+fn test_players_see_own_mission() {
+    let player1 = Rc::new(TestPlayer::new("TestPlayer1", Magenta));
+    let player2 = Rc::new(TestPlayer::new("TestPlayer2", Blue));
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::clone(&player1) as Rc<dyn Player>,
+        Rc::clone(&player2) as Rc<dyn Player>,
+    ];
+    let rules = Rules {
+        secret_missions: true,
+        turn_order: TurnOrder::Fixed,
+        ..Rules::default()
+    };
+    let mut game = Game::with_rules(players, peaceful_board(), rules);
+    game.setup(false);
+    let own = Mission::OccupyTerritories(3);
+    let other = Mission::HoldTerritories {
+        territories: 2,
+        armies: 5,
+    };
+    game.get_seats()[0].set_mission(Some(own.clone()));
+    game.get_seats()[1].set_mission(Some(other.clone()));
+
+    while player2.seen_missions.borrow().is_empty() {
+        assert!(game.step(false));
+    }
+    assert_eq!(*player1.seen_missions.borrow(), vec![Some(own)]);
+    assert_eq!(*player2.seen_missions.borrow(), vec![Some(other)]);
+
+    // The mission of the other player is not part of the view at all
+    let history = game.get_history().borrow();
+    let view = GameView::new(
+        game.get_board(),
+        game.get_players(),
+        game.get_seats(),
+        0,
+        FogOfWar::Disabled,
+        game.get_turn(),
+        &history,
+    );
+    let json = serde_json::to_string(&view).unwrap();
+    assert!(json.contains(r#""mission":{"OccupyTerritories":3}"#));
+    assert!(!json.contains("HoldTerritories"));
+}
+
+/// Under fog of war the engine hands every player its own fogged view
+#[test]
+fn test_fog_of_war_game() {
+    // The fourth territory is only adjacent to the second
+    let continent = Rc::new(Continent::new("TestContinent", 2, 2, 4));
+    let territories: Vec<Rc<Territory>> = (1..=4)
+        .map(|i| {
+            Rc::new(Territory::new(
+                &format!("TestTerritory{i}"),
+                Rc::clone(&continent),
+            ))
+        })
+        .collect();
+    territories[0].create_connections(vec![&territories[1]]);
+    territories[1].create_connections(vec![&territories[0], &territories[3]]);
+    territories[3].create_connections(vec![&territories[1]]);
+    let board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        territories.iter().collect(),
+        0,
+        None,
+    );
+
+    let rules = Rules {
+        dice: Rc::new(ExpectedDice::default()),
+        fog_of_war: FogOfWar::Hidden,
+        turn_order: TurnOrder::Fixed,
+        ..Rules::default()
+    };
+    let (player1, _, _) = play_on(board, rules);
+
+    // Before its first attack the first player owns the first and third territory
+    let seen = player1.seen_boards.borrow();
+    let visibility: Vec<Visibility> = seen[0]
+        .territories
+        .iter()
+        .map(|territory| territory.visibility)
+        .collect();
+    assert_eq!(
+        visibility,
+        vec![
+            Visibility::Full,
+            Visibility::Full,
+            Visibility::Full,
+            Visibility::Hidden
+        ]
+    );
+    assert_eq!(seen[0].player, 0);
+    assert_eq!(seen[0].territories[1].owner, Some(1));
+    assert_eq!(seen[0].territories[3].owner, None);
+    assert_eq!(seen[0].territories[3].armies, None);
+
+    // Once the second territory is captured, the fourth comes into sight
+    assert!(seen.iter().any(|board| {
+        board.territories[1].owner == Some(0)
+            && board.territories[3].visibility == Visibility::Full
+            && board.territories[3].owner == Some(1)
+    }));
+}
+
+/// Two territories that can never attack each other
+fn peaceful_board() -> BoardStruct {
+    let continent = Rc::new(Continent::new("TestContinent", 2, 2, 2));
+    let territory1 = Rc::new(Territory::new("TestTerritory1", Rc::clone(&continent)));
+    let territory2 = Rc::new(Territory::new("TestTerritory2", Rc::clone(&continent)));
+    territory1.create_typed_connections(vec![(&territory2, ConnectionType::FortifyOnly)]);
+    territory2.create_typed_connections(vec![(&territory1, ConnectionType::FortifyOnly)]);
+    BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        vec![&territory1, &territory2],
+        0,
+        None,
+    )
+}
+
+/// Rounds without captures end the game in a draw
+#[test]
+fn test_no_captures_draw() {
+    let rules = Rules {
+        stalemate: StalemateRules {
+            rounds_without_capture: Some(3),
+            response: StalemateResponse::Draw,
+            ..StalemateRules::default()
+        },
+        ..Rules::default()
+    };
+    let (_, _, game) = play_on(peaceful_board(), rules);
+    let result = game.get_result().unwrap();
+    assert!(result.is_draw());
+    assert_eq!(result.victory, Victory::Draw(Stalemate::NoCaptures(3)));
+    assert_eq!(game.get_round(), 3);
+    assert_eq!(game.get_winner(), None);
+}
+
+/// Exhausted players in the same position end the game with a tiebreak,
+/// stalemates that only get recorded let the game continue
+#[test]
+fn test_exhaustion_and_repetitions() {
+    let rules = Rules {
+        stalemate: StalemateRules {
+            exhaustion: true,
+            repetitions: Some(2),
+            response: StalemateResponse::Continue,
+            ..StalemateRules::default()
+        },
+        ..Rules::default()
+    };
+    let (_, _, game) = play_on(peaceful_board(), rules);
+    assert_eq!(
+        game.get_result().unwrap().victory,
+        Victory::Tiebreak(Tiebreak::Territories)
+    );
+    let stalemates: Vec<Stalemate> = game
+        .get_history()
+        .borrow()
+        .iter()
+        .filter_map(|event| match event {
+            Event::Stalemate { stalemate, .. } => Some(*stalemate),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        stalemates,
+        vec![Stalemate::Exhaustion, Stalemate::RepeatedPosition(2)]
+    );
+
+    let rules = Rules {
+        stalemate: StalemateRules {
+            exhaustion: true,
+            response: StalemateResponse::Tiebreak,
+            ..StalemateRules::default()
+        },
+        ..Rules::default()
+    };
+    let (_, _, game) = play_on(peaceful_board(), rules);
+    assert!(matches!(
+        game.get_result().unwrap().victory,
+        Victory::Stalemate(Stalemate::Exhaustion, Tiebreak::Territories)
+    ));
+    assert!(game.get_turn() < 100);
+}
+
+/// Plays a game on the peaceful board in which the first player behaves as given
+fn play_forfeit(rules: Rules, first: TestPlayer) -> Game {
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(first),
+        Rc::new(TestPlayer::new("TestPlayer2", Blue)),
+    ];
+    let rules = Rules {
+        turn_order: TurnOrder::Fixed,
+        ..rules
+    };
+    let mut game = Game::with_rules(players, peaceful_board(), rules);
+    game.setup(false);
+    game.play(None, Some(100), false, false);
+    game
+}
+
+/// The forfeits recorded in the history of the game
+fn forfeits(game: &Game) -> Vec<(usize, ForfeitReason)> {
+    game.get_history()
+        .borrow()
+        .iter()
+        .filter_map(|event| match event {
+            Event::Forfeit { player, reason } => Some((*player, *reason)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A player that surrenders leaves its territories to neutral armies
+#[test]
+fn test_surrender() {
+    let player = TestPlayer::new("TestPlayer1", Magenta);
+    *player.surrenders.borrow_mut() = true;
+    let game = play_forfeit(Rules::default(), player);
+
+    assert_eq!(forfeits(&game), vec![(0, ForfeitReason::Surrender)]);
+    let result = game.get_result().unwrap();
+    assert_eq!(result.ranking, vec![1, 0]);
+    assert_eq!(result.victory, Victory::Domination);
+    assert_eq!(game.get_turn(), 1);
+
+    assert!(game.get_board().territories[0].is_neutral());
+    assert_eq!(*game.get_seats()[0].armies.borrow(), 0);
+    assert!(game.get_seats()[0].get_territories().borrow().is_empty());
+}
+
+/// Players that forfeited no longer take turns in later rounds
+#[test]
+fn test_forfeited_players_skip_rounds() {
+    let continent = Rc::new(Continent::new("TestContinent", 3, 2, 3));
+    let territories: Vec<Rc<Territory>> = (1..=3)
+        .map(|i| {
+            Rc::new(Territory::new(
+                &format!("TestTerritory{i}"),
+                Rc::clone(&continent),
+            ))
+        })
+        .collect();
+    for territory in &territories {
+        territory.create_typed_connections(
+            territories
+                .iter()
+                .filter(|other| other.name != territory.name)
+                .map(|other| (other, ConnectionType::FortifyOnly))
+                .collect(),
+        );
+    }
+    let board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        territories.iter().collect(),
+        0,
+        None,
+    );
+
+    let surrendering = TestPlayer::new("TestPlayer2", Blue);
+    *surrendering.surrenders.borrow_mut() = true;
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(TestPlayer::new("TestPlayer1", Magenta)),
+        Rc::new(surrendering),
+        Rc::new(TestPlayer::new("TestPlayer3", White)),
+    ];
+    let rules = Rules {
+        turn_order: TurnOrder::Fixed,
+        ..Rules::default()
+    };
+    let mut game = Game::with_rules(players, board, rules);
+    game.setup(false);
+    while game.get_round() < 3 && game.step_turn(false) {}
+
+    let orders: Vec<Vec<usize>> = game
+        .get_history()
+        .borrow()
+        .iter()
+        .filter_map(|event| match event {
+            Event::Round { order, .. } => Some(order.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(orders, vec![vec![0, 1, 2], vec![0, 2], vec![0, 2]]);
+}
+
+/// Players that take too long forfeit, removed players leave a single neutral army behind
+#[test]
+fn test_time_controls() {
+    let player = TestPlayer::new("TestPlayer1", Magenta);
+    *player.delay.borrow_mut() = Duration::from_millis(20);
+    let rules = Rules {
+        decision_time: Some(Duration::from_millis(10)),
+        forfeit: Forfeit::Remove,
+        ..Rules::default()
+    };
+    let game = play_forfeit(rules, player);
+    assert_eq!(forfeits(&game), vec![(0, ForfeitReason::DecisionTime)]);
+    assert_eq!(game.get_winner(), Some(1));
+    assert!(game.get_board().territories[0].is_neutral());
+    assert_eq!(*game.get_board().territories[0].armies.borrow(), 1);
+
+    let player = TestPlayer::new("TestPlayer1", Magenta);
+    *player.delay.borrow_mut() = Duration::from_millis(5);
+    let rules = Rules {
+        game_time: Some(Duration::from_millis(12)),
+        ..Rules::default()
+    };
+    let game = play_forfeit(rules, player);
+    assert_eq!(forfeits(&game), vec![(0, ForfeitReason::GameTime)]);
+    assert_eq!(game.get_winner(), Some(1));
+    assert!(game.get_round() >= 3);
+}
+
+/// Panics of players are reported and handled as the rules decide
+#[test]
+fn test_panics() {
+    let crashing = || {
+        let player = TestPlayer::new("TestPlayer1", Magenta);
+        *player.panics.borrow_mut() = true;
+        player
+    };
+
+    let rules = Rules {
+        on_panic: PanicFallback::Default,
+        ..Rules::default()
+    };
+    let game = play_forfeit(rules, crashing());
+    let panics = game.get_panics().borrow();
+    assert!(!panics.is_empty());
+    assert!(panics.iter().all(|report| report.player == 0
+        && report.decision == Decision::Attack
+        && report.message == "TestPlayer crashed"
+        && report.board.len() == 2));
+    assert!(forfeits(&game).is_empty());
+
+    let rules = Rules {
+        on_panic: PanicFallback::Forfeit,
+        ..Rules::default()
+    };
+    let game = play_forfeit(rules, crashing());
+    assert_eq!(game.get_panics().borrow().len(), 1);
+    assert_eq!(forfeits(&game), vec![(0, ForfeitReason::Panic)]);
+    assert_eq!(game.get_winner(), Some(1));
+
+    let aborted = panic::catch_unwind(AssertUnwindSafe(|| {
+        play_forfeit(Rules::default(), crashing());
+    }));
+    assert!(aborted.is_err());
+}
+
+/// Invalid decisions fall back the same way as panics, instead of crashing the game
+#[test]
+fn test_invalid_decisions() {
+    let player1 = Rc::new(TestPlayer::new("TestPlayer1", Magenta));
+    *player1.cheats.borrow_mut() = true;
+    let players: Vec<Rc<dyn Player>> = vec![player1, Rc::new(TestPlayer::new("TestPlayer2", Blue))];
+    let rules = Rules {
+        on_panic: PanicFallback::Default,
+        turn_order: TurnOrder::Fixed,
+        ..Rules::default()
+    };
+    let mut game = Game::with_rules(players, board(), rules);
+    game.setup(false);
+    game.play(None, Some(100), false, false);
+
+    let panics = game.get_panics().borrow();
+    for decision in [Decision::Claim, Decision::Placement, Decision::Attack] {
+        assert!(panics
+            .iter()
+            .any(|report| report.player == 0 && report.decision == decision));
+    }
+    assert!(panics.iter().all(|report| report.player == 0));
+    assert_eq!(game.get_winner(), Some(1));
+
+    let aborted = panic::catch_unwind(AssertUnwindSafe(|| {
+        let player1 = TestPlayer::new("TestPlayer1", Magenta);
+        *player1.cheats.borrow_mut() = true;
+        play_forfeit(Rules::default(), player1);
+    }));
+    assert!(aborted.is_err());
+}
+
+/// Defeated players no longer take turns in later rounds
+#[test]
+fn test_defeated_players_skip_rounds() {
+    // Two pairs of territories that can only attack within the pair
+    let continent = Rc::new(Continent::new("TestContinent", 3, 2, 4));
+    let territories: Vec<Rc<Territory>> = (1..=4)
+        .map(|i| {
+            Rc::new(Territory::new(
+                &format!("TestTerritory{i}"),
+                Rc::clone(&continent),
+            ))
+        })
+        .collect();
+    for (i, territory) in territories.iter().enumerate() {
+        territory.create_typed_connections(
+            territories
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| i != *j)
+                .map(|(j, other)| match i / 2 == j / 2 {
+                    true => (other, ConnectionType::Land),
+                    false => (other, ConnectionType::FortifyOnly),
+                })
+                .collect(),
+        );
+    }
+    let board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        territories.iter().collect(),
+        0,
+        None,
+    );
+
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(TestPlayer::new("TestPlayer1", Magenta)),
+        Rc::new(TestPlayer::new("TestPlayer2", Blue)),
+        Rc::new(TestPlayer::new("TestPlayer3", White)),
+    ];
+    let rules = Rules {
+        dice: Rc::new(ExpectedDice::default()),
+        turn_order: TurnOrder::Fixed,
+        ..Rules::default()
+    };
+    let mut game = Game::with_rules(players, board, rules);
+    game.setup(false);
+    while game.get_round() < 3 && game.step_turn(false) {}
+
+    let history = game.get_history().borrow();
+    assert!(history.contains(&Event::Defeat { player: 1, by: 0 }));
+    let orders: Vec<Vec<usize>> = history
+        .iter()
+        .filter_map(|event| match event {
+            Event::Round { order, .. } => Some(order.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(orders, vec![vec![0, 1, 2], vec![0, 2], vec![0, 2]]);
+}
@@ -0,0 +1,163 @@
+use colored::Color::{Blue, Magenta, White};
+use std::rc::Rc;
+
+use rist::boards::{BoardStruct, BoardType};
+use rist::continent::Continent;
+use rist::missions::{deal_missions, generate_missions, Mission};
+use rist::players::{generate_ids, Player, RandomPlayer, Seat};
+use rist::territory::Territory;
+
+fn players() -> Vec<Rc<dyn Player>> {
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(RandomPlayer::new("TestPlayer1", Magenta, White)),
+        Rc::new(RandomPlayer::new("TestPlayer2", Blue, White)),
+    ];
+    generate_ids(&players);
+    players
+}
+
+/// A small custom board with three continents of sizes 1, 1 and 2
+fn board() -> BoardStruct {
+    let continent1 = Rc::new(Continent::new("TestContinent1", 2, 2, 1));
+    let continent2 = Rc::new(Continent::new("TestContinent2", 2, 2, 1));
+    let continent3 = Rc::new(Continent::new("TestContinent3", 2, 3, 2));
+
+    let territory1 = Rc::new(Territory::new("TestTerritory1", Rc::clone(&continent1)));
+    let territory2 = Rc::new(Territory::new("TestTerritory2", Rc::clone(&continent2)));
+    let territory3 = Rc::new(Territory::new("TestTerritory3", Rc::clone(&continent3)));
+    let territory4 = Rc::new(Territory::new("TestTerritory4", Rc::clone(&continent3)));
+
+    BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent1, &continent2, &continent3],
+        vec![&territory1, &territory2, &territory3, &territory4],
+        0,
+        None,
+    )
+}
+
+/// Missions are generated from the continents and size of the board
+#[test]
+fn test_generate_missions() {
+    let board = board();
+    let players = players();
+    let missions = generate_missions(&board, &players);
+
+    // Only the two continents of size 1 make up at most half of the board
+    assert!(missions.contains(&Mission::ConquerContinents {
+        continents: vec![
+            Rc::clone(&board.continents[0]),
+            Rc::clone(&board.continents[1])
+        ],
+        additional: 0,
+    }));
+    assert!(missions.contains(&Mission::OccupyTerritories(3)));
+    assert!(missions.contains(&Mission::HoldTerritories {
+        territories: 2,
+        armies: 2
+    }));
+    assert_eq!(missions.len(), 5);
+
+    for mission in &missions {
+        println!("{}", mission);
+    }
+}
+
+/// No player is dealt the mission to eliminate itself
+#[test]
+fn test_deal_missions() {
+    let missions = vec![
+        Mission::EliminatePlayer {
+            target: 0,
+            name: String::from("TestPlayer1"),
+            fallback: 3,
+        },
+        Mission::EliminatePlayer {
+            target: 1,
+            name: String::from("TestPlayer2"),
+            fallback: 3,
+        },
+    ];
+
+    let seats = [Seat::new(), Seat::new()];
+    deal_missions(missions, &seats);
+
+    for (index, seat) in seats.iter().enumerate() {
+        match seat.get_mission().borrow().as_ref().unwrap() {
+            Mission::EliminatePlayer { target, .. } => assert_ne!(*target, index),
+            Mission::OccupyTerritories(territories) => assert_eq!(*territories, 3),
+            mission => panic!("Unexpected mission {}", mission),
+        }
+    }
+}
+
+#[test]
+fn test_conquer_continents_fulfilled() {
+    let board = board();
+    let seats = [Seat::new(), Seat::new()];
+    let state = &seats[0];
+    let mission = Mission::ConquerContinents {
+        continents: vec![Rc::clone(&board.continents[0])],
+        additional: 1,
+    };
+
+    state.add_continent(Rc::clone(&board.continents[0]));
+    assert!(!mission.is_fulfilled(0, &seats));
+
+    state.add_continent(Rc::clone(&board.continents[2]));
+    assert!(mission.is_fulfilled(0, &seats));
+}
+
+#[test]
+fn test_hold_territories_fulfilled() {
+    let board = board();
+    let seats = [Seat::new(), Seat::new()];
+    let state = &seats[0];
+    let mission = Mission::HoldTerritories {
+        territories: 2,
+        armies: 2,
+    };
+
+    for territory in &board.territories[0..2] {
+        state.add_territory(Rc::clone(territory));
+        *territory.armies.borrow_mut() = 1;
+    }
+    assert!(!mission.is_fulfilled(0, &seats));
+
+    *board.territories[1].armies.borrow_mut() = 2;
+    assert!(!mission.is_fulfilled(0, &seats));
+
+    *board.territories[0].armies.borrow_mut() = 3;
+    assert!(mission.is_fulfilled(0, &seats));
+}
+
+/// Eliminating the target fulfills the mission,
+/// when eliminated by someone else the mission falls back to occupying territories
+#[test]
+fn test_eliminate_player_fallback() {
+    let board = board();
+    let seats = [Seat::new(), Seat::new()];
+    let state = &seats[0];
+    let target = &seats[1];
+    let mission = Mission::EliminatePlayer {
+        target: 1,
+        name: String::from("TestPlayer2"),
+        fallback: 3,
+    };
+
+    target.add_territory(Rc::clone(&board.territories[0]));
+    assert!(!mission.is_fulfilled(0, &seats));
+
+    target.remove_territory(&board.territories[0]);
+    *target.defeated_by.borrow_mut() = Some(0);
+    assert!(mission.is_fulfilled(0, &seats));
+
+    // Defeated by a third player
+    *target.defeated_by.borrow_mut() = Some(2);
+    assert!(!mission.is_fulfilled(0, &seats));
+
+    for territory in &board.territories[0..3] {
+        state.add_territory(Rc::clone(territory));
+    }
+    assert!(mission.is_fulfilled(0, &seats));
+}
@@ -0,0 +1,30 @@
+use rist::modifiers::{apply_defense_bonus, defense_bonus, Modifier};
+
+/// Modifiers are written and parsed in the same format
+#[test]
+fn test_modifier_parsing() {
+    for modifier in [Modifier::Fortress, Modifier::City(2), Modifier::Scar] {
+        assert_eq!(modifier.to_string().parse::<Modifier>(), Ok(modifier));
+    }
+    assert!("city".parse::<Modifier>().is_err());
+    assert!("castle".parse::<Modifier>().is_err());
+}
+
+/// The defense bonus only changes the highest roll of the defender
+#[test]
+fn test_defense_bonus() {
+    let mut rolls = vec![3, 5];
+    apply_defense_bonus(&mut rolls, defense_bonus(&[Modifier::Fortress]));
+    assert_eq!(rolls, vec![3, 6]);
+
+    let mut rolls = vec![1];
+    apply_defense_bonus(
+        &mut rolls,
+        defense_bonus(&[Modifier::Scar, Modifier::City(3)]),
+    );
+    assert_eq!(rolls, vec![1]);
+
+    let mut rolls = vec![2, 6];
+    apply_defense_bonus(&mut rolls, defense_bonus(&[Modifier::Fortress]));
+    assert_eq!(rolls, vec![2, 7]);
+}
@@ -0,0 +1,141 @@
+use colored::Color::{Blue, Magenta, White};
+use std::rc::Rc;
+
+use rist::boards::{BoardStruct, BoardType};
+use rist::continent::Continent;
+use rist::history::Event;
+use rist::players::{generate_ids, Player, RandomPlayer, Seat};
+use rist::reinforcements::{
+    ConnectedReinforcements, FixedReinforcements, PartialContinentReinforcements,
+    ProgressiveContinentReinforcements, ReinforcementPolicy, Reinforcements,
+    TerritoryReinforcements,
+};
+use rist::rules::FogOfWar;
+use rist::territory::Territory;
+use rist::view::GameView;
+
+/// A line of four territories in a single continent, claimed by player 0 except for the third
+fn claimed_board(players: &[Rc<dyn Player>], seats: &[Seat]) -> BoardStruct {
+    let continent = Rc::new(Continent::new("TestContinent", 2, 4, 4));
+    let territories: Vec<Rc<Territory>> = (1..=4)
+        .map(|i| {
+            Rc::new(Territory::new(
+                &format!("TestTerritory{i}"),
+                Rc::clone(&continent),
+            ))
+        })
+        .collect();
+    for (i, territory) in territories.iter().enumerate() {
+        let mut connections = vec![];
+        if i > 0 {
+            connections.push(&territories[i - 1]);
+        }
+        if i < 3 {
+            connections.push(&territories[i + 1]);
+        }
+        territory.create_connections(connections);
+    }
+
+    let mut board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        territories.iter().collect(),
+        0,
+        None,
+    );
+    for seat in seats {
+        *seat.armies.borrow_mut() = 4;
+    }
+    for owner in [0, 0, 1, 0] {
+        board.claim_territory(0, Rc::clone(&players[owner]), &seats[owner], false);
+    }
+    board
+}
+
+fn players() -> Vec<Rc<dyn Player>> {
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(RandomPlayer::new("TestPlayer1", Magenta, White)),
+        Rc::new(RandomPlayer::new("TestPlayer2", Blue, White)),
+    ];
+    generate_ids(&players);
+    players
+}
+
+fn total(policy: &dyn ReinforcementPolicy, game: &GameView) -> u32 {
+    policy
+        .reinforcements(game)
+        .iter()
+        .map(|(_, armies)| armies)
+        .sum()
+}
+
+#[test]
+fn test_reinforcement_policies() {
+    let players = players();
+    let seats = [Seat::new(), Seat::new()];
+    let board = claimed_board(&players, &seats);
+    let view = GameView::new(&board, &players, &seats, 0, FogOfWar::Disabled, 1, &[]);
+
+    assert_eq!(total(&Reinforcements::classic(), &view), 3);
+    assert_eq!(total(&FixedReinforcements(5), &view), 5);
+    let territories = TerritoryReinforcements {
+        divisor: 1,
+        minimum: 0,
+    };
+    assert_eq!(total(&territories, &view), 3);
+    let partial = PartialContinentReinforcements { share: 0.5 };
+    assert_eq!(total(&partial, &view), 3);
+    assert_eq!(total(&ConnectedReinforcements { divisor: 1 }, &view), 2);
+
+    // A divisor of 0 rewards nothing for the territories
+    let territories = TerritoryReinforcements {
+        divisor: 0,
+        minimum: 3,
+    };
+    assert_eq!(total(&territories, &view), 3);
+    assert_eq!(total(&ConnectedReinforcements { divisor: 0 }, &view), 0);
+}
+
+/// Progressive continent rewards decay the longer the continent is held
+#[test]
+fn test_progressive_continent_reinforcements() {
+    let players = players();
+    let seats = [Seat::new(), Seat::new()];
+    let board = claimed_board(&players, &seats);
+    let mut history: Vec<Event> = [0, 0, 1, 0]
+        .iter()
+        .enumerate()
+        .map(|(territory, player)| Event::Claim {
+            player: *player,
+            territory,
+        })
+        .collect();
+    history.push(Event::Capture {
+        player: 0,
+        from: 1,
+        territory: 2,
+    });
+    board.territories[2].set_player(Some(Rc::downgrade(&players[0])));
+
+    let policy = ProgressiveContinentReinforcements { decay: 2 };
+    let mut rewards = vec![];
+    for turn in 1..=5 {
+        history.push(Event::Turn { turn, player: 0 });
+        let view = GameView::new(
+            &board,
+            &players,
+            &seats,
+            0,
+            FogOfWar::Disabled,
+            turn,
+            &history,
+        );
+        rewards.push(total(&policy, &view));
+    }
+    assert_eq!(rewards, vec![4, 4, 3, 3, 2]);
+
+    // Without decay the reward never decreases
+    let policy = ProgressiveContinentReinforcements { decay: 0 };
+    let view = GameView::new(&board, &players, &seats, 0, FogOfWar::Disabled, 5, &history);
+    assert_eq!(total(&policy, &view), 4);
+}
@@ -0,0 +1,132 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::rc::Rc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use rist::players::Player;
+use rist::server::{Hello, Server};
+use rist::victory::{Tiebreak, Victory};
+use rist::Game;
+
+mod common;
+use common::board;
+
+/// The reply of a bot that never places or attacks
+fn reply(message: &Value) -> Option<Value> {
+    match message["type"].as_str().unwrap() {
+        "claim" => Some(json!(0)),
+        "defend" => Some(json!(1)),
+        "place" | "commanders" => Some(json!([])),
+        "attack" | "fortify" => Some(Value::Null),
+        "capture" => Some(message["attack"]["dice"].clone()),
+        "defend_blitz" => Some(json!("MaxDice")),
+        "surrender" => Some(json!(false)),
+        _ => None,
+    }
+}
+
+/// Connects to the server and plays passively until the game is over,
+/// leaving after the given amount of replies and rejoining right away.
+/// Returns all lines the client received.
+fn client(address: SocketAddr, hello: Hello, leave_after: Option<usize>) -> JoinHandle<Vec<Value>> {
+    thread::spawn(move || {
+        let mut received = vec![];
+        let mut hello = hello;
+        let mut replies = 0;
+        'connection: loop {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.set_nodelay(true).unwrap();
+            writeln!(stream, "{}", serde_json::to_string(&hello).unwrap()).unwrap();
+            for line in BufReader::new(stream.try_clone().unwrap()).lines() {
+                let message: Value = serde_json::from_str(&line.unwrap()).unwrap();
+                received.push(message.clone());
+                match message["type"].as_str().unwrap() {
+                    "seat" => {
+                        hello = Hello::Rejoin(String::from(message["token"].as_str().unwrap()))
+                    }
+                    "game_over" | "error" => break 'connection,
+                    _ => {}
+                }
+                if let Some(reply) = reply(&message) {
+                    writeln!(stream, "{reply}").unwrap();
+                    replies += 1;
+                    if Some(replies) == leave_after {
+                        received.push(json!({"type": "rejoin"}));
+                        continue 'connection;
+                    }
+                }
+            }
+        }
+        received
+    })
+}
+
+fn host(server: &Server) -> Game {
+    let players: Vec<Rc<dyn Player>> = server
+        .players(Duration::from_secs(5))
+        .into_iter()
+        .map(|player| Rc::new(player) as Rc<dyn Player>)
+        .collect();
+    let mut game = Game::new(players, board());
+    game.setup(false);
+    game
+}
+
+/// Remote players play an entire game that spectators follow
+#[test]
+fn test_server() {
+    let server = Server::new("127.0.0.1:0", 2).unwrap();
+    let address = server.address();
+    let spectator = client(address, Hello::Spectate, None);
+    let first = client(address, Hello::Join(String::from("First")), None);
+    let second = client(address, Hello::Join(String::from("Second")), None);
+
+    let mut game = host(&server);
+    let result = server.play(&mut game, Some(20));
+    assert_eq!(result.victory, Victory::Tiebreak(Tiebreak::Territories));
+    assert!(game.get_panics().borrow().is_empty());
+
+    let full = client(address, Hello::Join(String::from("Third")), None);
+    assert_eq!(full.join().unwrap()[0]["type"], "error");
+
+    for player in [first, second] {
+        let received = player.join().unwrap();
+        assert_eq!(received[0]["type"], "seat");
+        assert_eq!(received.last().unwrap()["type"], "game_over");
+    }
+
+    let received = spectator.join().unwrap();
+    let events = received
+        .iter()
+        .filter(|message| message["type"] == "event")
+        .count();
+    assert_eq!(events, game.get_history().borrow().len());
+    assert_eq!(
+        received.last().unwrap()["result"]["ranking"],
+        json!(result.ranking)
+    );
+}
+
+/// A player that leaves takes back its seat and receives the entire history again
+#[test]
+fn test_rejoin() {
+    let server = Server::new("127.0.0.1:0", 2).unwrap();
+    let address = server.address();
+    let leaving = client(address, Hello::Join(String::from("Leaving")), Some(3));
+    let staying = client(address, Hello::Join(String::from("Staying")), None);
+
+    let mut game = host(&server);
+    server.play(&mut game, Some(10));
+    assert!(game.get_panics().borrow().is_empty());
+    staying.join().unwrap();
+
+    let received = leaving.join().unwrap();
+    assert!(received
+        .iter()
+        .skip_while(|message| message["type"] != "rejoin")
+        .any(|message| message["events"][0]["Claim"].is_object()));
+    assert_eq!(received.last().unwrap()["type"], "game_over");
+}
@@ -1,7 +1,7 @@
 use std::rc::Rc;
 
 use rist::continent::Continent;
-use rist::territory::{generate_ids, Territory};
+use rist::territory::{generate_ids, ConnectionType, Territory};
 
 /// Test the territory display visually
 #[test]
@@ -73,3 +73,36 @@ fn test_territory_id_generation() {
     assert_eq!(*territory2.index.borrow(), 1);
     assert_eq!(*territory3.index.borrow(), 2);
 }
+
+/// Test the types of one-way and typed connections
+#[test]
+fn test_connection_types() {
+    let continent = Rc::new(Continent::new("TestContinent", 4, 5, 3));
+    let territory1 = Rc::new(Territory::new("TestTerritory1", Rc::clone(&continent)));
+    let territory2 = Rc::new(Territory::new("TestTerritory2", Rc::clone(&continent)));
+    let territory3 = Rc::new(Territory::new("TestTerritory3", Rc::clone(&continent)));
+    generate_ids(&vec![&territory1, &territory2, &territory3]);
+
+    territory1.create_typed_connections(vec![
+        (&territory2, ConnectionType::Sea),
+        (&territory3, ConnectionType::AttackOnly),
+    ]);
+    territory2.create_connections(vec![&territory1]);
+
+    assert_eq!(
+        territory1.connection_to(&territory2),
+        Some(ConnectionType::Sea)
+    );
+    assert_eq!(
+        territory2.connection_to(&territory1),
+        Some(ConnectionType::Land)
+    );
+    assert_eq!(territory3.connection_to(&territory1), None);
+    assert_eq!(ConnectionType::Sea.max_attack_dice(), 2);
+    assert!(!ConnectionType::AttackOnly.allows_fortify());
+    assert!(!ConnectionType::FortifyOnly.allows_attack());
+
+    let display = territory1.to_string();
+    assert!(display.contains("TestTerritory2 (sea),"));
+    assert!(display.contains("TestTerritory3 (attack only) (one-way)"));
+}
@@ -0,0 +1,243 @@
+use colored::Color::{Blue, Red, White};
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+
+use ratatui::backend::TestBackend;
+use ratatui::crossterm::event::KeyCode::{self, Char, Down, Enter, Esc, PageUp};
+use ratatui::Terminal;
+
+use rist::boards::{BoardStruct, BoardType};
+use rist::continent::Continent;
+use rist::dice::ScriptedDice;
+use rist::players::{HumanPlayer, Player, RandomPlayer};
+use rist::rules::{FogOfWar, Rules};
+use rist::territory::{ConnectionType, Territory};
+use rist::tui::Tui;
+use rist::view::GameView;
+use rist::{Attack, Game};
+
+mod common;
+use common::board;
+
+fn tui(keys: Vec<KeyCode>) -> Tui<TestBackend> {
+    let terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+    Tui::with_terminal(terminal, Box::new(VecDeque::from(keys)))
+}
+
+fn screen(tui: &Tui<TestBackend>) -> String {
+    tui.get_terminal().borrow().backend().to_string()
+}
+
+/// Four territories in a single continent that can only fortify each other, so no dice are rolled
+fn peaceful_board() -> BoardStruct {
+    let continent = Rc::new(Continent::new("TestContinent", 2, 2, 4));
+    let territories: Vec<Rc<Territory>> = (1..=4)
+        .map(|i| {
+            Rc::new(Territory::new(
+                &format!("TestTerritory{i}"),
+                Rc::clone(&continent),
+            ))
+        })
+        .collect();
+    for territory in &territories {
+        territory.create_typed_connections(
+            territories
+                .iter()
+                .filter(|other| other.name != territory.name)
+                .map(|other| (other, ConnectionType::FortifyOnly))
+                .collect(),
+        );
+    }
+    BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        territories.iter().collect(),
+        0,
+        None,
+    )
+}
+
+/// A game is sped up, paused, stepped, resumed and followed until it ends
+#[test]
+fn test_tui() {
+    let tui = tui(vec![
+        Char('+'),
+        Char(' '),
+        Char('n'),
+        Char('n'),
+        Char(' '),
+        PageUp,
+    ]);
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(RandomPlayer::new("First", Red, White)),
+        Rc::new(RandomPlayer::new("Second", Blue, White)),
+    ];
+    let rules = Rules {
+        dice: Rc::new(ScriptedDice::new(vec![])),
+        ..Rules::default()
+    };
+    let mut game = Game::with_rules(players, peaceful_board(), rules);
+    tui.setup(&mut game);
+    tui.play(&mut game, Some(50));
+
+    // The game only ends at the turn limit, after all keys have been pressed
+    assert_eq!(game.get_turn(), 50);
+    let screen = screen(&tui);
+    assert!(screen.contains("Game over"));
+    assert!(screen.contains("100 ms between phases"));
+    assert!(screen.contains("Second"));
+
+    // Every territory is followed by the territories it connects to
+    let territory = screen
+        .lines()
+        .find(|line| line.contains("TestTerritory4"))
+        .unwrap();
+    assert!(territory.contains("3 TestTerritory4"), "{screen}");
+    assert!(territory.contains(" 0 1 2"), "{screen}");
+
+    let tui = self::tui(vec![Char('q')]);
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(RandomPlayer::new("First", Red, White)),
+        Rc::new(RandomPlayer::new("Second", Blue, White)),
+    ];
+    let mut game = Game::new(players, board());
+    tui.setup(&mut game);
+    tui.play(&mut game, None);
+    assert_eq!(game.get_turn(), 0);
+}
+
+/// A human player claims, places and attacks with the keyboard
+#[test]
+fn test_human_player() {
+    // Claim the second and the first free territory
+    let claim = [Down, Enter, Enter];
+    // Place all armies but one on the first territory
+    let place = [Enter, Down, Enter, Esc];
+    // Attack the first target with as many dice as possible, then stop
+    let attack = [Enter, Enter, Enter, Esc];
+    let tui = tui([&claim[..], &place, &attack].concat());
+    let human: Rc<HumanPlayer<TestBackend>> = Rc::new(tui.human("Human", Red, White));
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::clone(&human) as Rc<dyn Player>,
+        Rc::new(RandomPlayer::new("Random", Blue, White)),
+    ];
+    let mut game = Game::new(players, board());
+    tui.setup(&mut game);
+    let territories: Vec<usize> = game.get_seats()[0]
+        .get_territories()
+        .borrow()
+        .iter()
+        .map(|territory| *territory.index.borrow())
+        .collect();
+    assert_eq!(territories.len(), 2);
+
+    let history = game.get_history().borrow();
+    let view = GameView::new(
+        game.get_board(),
+        game.get_players(),
+        game.get_seats(),
+        0,
+        FogOfWar::Disabled,
+        0,
+        &history,
+    );
+    let first = view.territories()[0].index;
+    assert_eq!(human.place_armies(&view), vec![(first, view.reserve() - 1)]);
+
+    // The armies are placed by hand, as the engine would
+    *game.get_board().territories[first].armies.borrow_mut() += view.reserve() - 1;
+    let view = GameView::new(
+        game.get_board(),
+        game.get_players(),
+        game.get_seats(),
+        0,
+        FogOfWar::Disabled,
+        0,
+        &history,
+    );
+    let target = view.board.territories[first]
+        .connections
+        .iter()
+        .copied()
+        .find(|territory| view.board.territories[*territory].owner == Some(1))
+        .unwrap();
+    assert_eq!(
+        human.attack(&view),
+        Some(Attack {
+            dice: 3,
+            attacker: first,
+            defender: target,
+            blitz: None,
+            roll: None,
+        })
+    );
+    assert_eq!(human.attack(&view), None);
+    assert!(screen(&tui).contains("Attack from, or stop attacking"));
+}
+
+/// A paused game stops and a question fails once the scripted keys have run out
+#[test]
+fn test_out_of_keys() {
+    let tui = tui(vec![Char(' ')]);
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(RandomPlayer::new("First", Red, White)),
+        Rc::new(RandomPlayer::new("Second", Blue, White)),
+    ];
+    let mut game = Game::new(players, board());
+    tui.setup(&mut game);
+    tui.play(&mut game, None);
+    assert_eq!(game.get_turn(), 0);
+
+    let tui = self::tui(vec![]);
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(tui.human("Human", Red, White)),
+        Rc::new(RandomPlayer::new("Random", Blue, White)),
+    ];
+    let mut game = Game::new(players, board());
+    let setup = panic::catch_unwind(AssertUnwindSafe(|| tui.setup(&mut game)));
+    assert!(setup.is_err());
+}
+
+/// Under fog of war the screen only shows what the human player sees
+#[test]
+fn test_tui_under_fog() {
+    // Four territories without any connections
+    let continent = Rc::new(Continent::new("TestContinent", 2, 2, 4));
+    let territories: Vec<Rc<Territory>> = (1..=4)
+        .map(|i| {
+            Rc::new(Territory::new(
+                &format!("TestTerritory{i}"),
+                Rc::clone(&continent),
+            ))
+        })
+        .collect();
+    let board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        territories.iter().collect(),
+        0,
+        None,
+    );
+
+    // Claim two territories and stop the game right away
+    let tui = tui(vec![Enter, Enter, Char('q')]);
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(tui.human("Human", Red, White)),
+        Rc::new(RandomPlayer::new("Random", Blue, White)),
+    ];
+    let rules = Rules {
+        fog_of_war: FogOfWar::Hidden,
+        ..Rules::default()
+    };
+    let mut game = Game::with_rules(players, board, rules);
+    tui.setup(&mut game);
+    tui.play(&mut game, None);
+
+    let screen = screen(&tui);
+    let hidden = screen
+        .lines()
+        .filter(|line| line.contains("TestTerritory") && line.contains('?'))
+        .count();
+    assert_eq!(hidden, 2, "{screen}");
+}
@@ -0,0 +1,181 @@
+use colored::Color::{Blue, Magenta, White};
+use std::rc::Rc;
+
+use rist::boards::{BoardStruct, BoardType};
+use rist::continent::Continent;
+use rist::history::Event;
+use rist::players::{generate_ids, Player, RandomPlayer, Seat};
+use rist::rules::FogOfWar;
+use rist::territory::Territory;
+use rist::view::{BoardView, GameView, Visibility};
+
+/// A line of three territories, the first claimed by player 0 and the others by player 1,
+/// together with the seats of the players
+fn claimed_board(players: &[Rc<dyn Player>]) -> (BoardStruct, Vec<Seat>) {
+    let continent = Rc::new(Continent::new("TestContinent", 2, 5, 3));
+
+    let territory1 = Rc::new(Territory::new("TestTerritory1", Rc::clone(&continent)));
+    let territory2 = Rc::new(Territory::new("TestTerritory2", Rc::clone(&continent)));
+    let territory3 = Rc::new(Territory::new("TestTerritory3", Rc::clone(&continent)));
+
+    territory1.create_connections(vec![&territory2]);
+    territory2.create_connections(vec![&territory1, &territory3]);
+    territory3.create_connections(vec![&territory2]);
+
+    let mut board = BoardStruct::generate_board(
+        BoardType::Unimplemented,
+        vec![&continent],
+        vec![&territory1, &territory2, &territory3],
+        0,
+        None,
+    );
+
+    let seats: Vec<Seat> = players.iter().map(|_| Seat::new()).collect();
+    for seat in &seats {
+        *seat.armies.borrow_mut() = 2;
+    }
+    for owner in [0, 1, 1] {
+        board.claim_territory(0, Rc::clone(&players[owner]), &seats[owner], false);
+    }
+    (board, seats)
+}
+
+fn players() -> Vec<Rc<dyn Player>> {
+    let players: Vec<Rc<dyn Player>> = vec![
+        Rc::new(RandomPlayer::new("TestPlayer1", Magenta, White)),
+        Rc::new(RandomPlayer::new("TestPlayer2", Blue, White)),
+    ];
+    generate_ids(&players);
+    players
+}
+
+/// Without fog of war the entire board is visible
+#[test]
+fn test_view_without_fog() {
+    let players = players();
+    let (board, _) = claimed_board(&players);
+    let view = BoardView::new(&board, 0, FogOfWar::Disabled);
+
+    for territory in &view.territories {
+        assert_eq!(territory.visibility, Visibility::Full);
+        assert_eq!(territory.armies, Some(1));
+    }
+    assert_eq!(view.territories[2].owner, Some(1));
+    assert_eq!(view.territories[1].connections, vec![0, 2]);
+    assert_eq!(view.continents[0].territories, vec![0, 1, 2]);
+}
+
+/// Only owned and adjacent territories show their armies
+#[test]
+fn test_view_with_fog_showing_owners() {
+    let players = players();
+    let (board, _) = claimed_board(&players);
+    let view = BoardView::new(&board, 0, FogOfWar::Owners);
+
+    assert_eq!(view.territories[0].armies, Some(1));
+    assert_eq!(view.territories[1].armies, Some(1));
+    assert_eq!(view.territories[2].visibility, Visibility::Owner);
+    assert_eq!(view.territories[2].owner, Some(1));
+    assert_eq!(view.territories[2].armies, None);
+}
+
+/// Territories out of sight reveal nothing
+#[test]
+fn test_view_with_hidden_fog() {
+    let players = players();
+    let (board, _) = claimed_board(&players);
+
+    let view = BoardView::new(&board, 0, FogOfWar::Hidden);
+    assert_eq!(view.territories[2].visibility, Visibility::Hidden);
+    assert_eq!(view.territories[2].owner, None);
+    assert_eq!(view.territories[2].armies, None);
+
+    // The second player is adjacent to everything
+    let view = BoardView::new(&board, 1, FogOfWar::Hidden);
+    for territory in &view.territories {
+        assert_eq!(territory.visibility, Visibility::Full);
+    }
+}
+
+/// The game view exposes the public state of all players and the history
+#[test]
+fn test_game_view() {
+    let players = players();
+    let (board, seats) = claimed_board(&players);
+    *seats[1].armies.borrow_mut() = 7;
+    let history = vec![Event::Claim {
+        player: 0,
+        territory: 0,
+    }];
+
+    let view = GameView::new(&board, &players, &seats, 0, FogOfWar::Disabled, 3, &history);
+
+    assert_eq!(view.player(), 0);
+    assert_eq!(view.turn, 3);
+    assert_eq!(view.reserve(), 1);
+    assert_eq!(view.players[1].reserve, 7);
+    assert_eq!(view.players[1].name, "TestPlayer2");
+    assert_eq!(view.history, &history[..]);
+    assert_eq!(
+        view.territories()
+            .iter()
+            .map(|territory| territory.index)
+            .collect::<Vec<usize>>(),
+        vec![0]
+    );
+}
+
+/// Under fog of war players only see the events they witnessed
+#[test]
+fn test_history_under_fog() {
+    let players = players();
+    let (board, seats) = claimed_board(&players);
+    let history = vec![
+        Event::Claim {
+            player: 0,
+            territory: 0,
+        },
+        Event::Claim {
+            player: 1,
+            territory: 2,
+        },
+        Event::Turn { turn: 1, player: 1 },
+        Event::Reinforcement {
+            player: 1,
+            armies: 3,
+        },
+        Event::Attack {
+            attacker: 1,
+            defender: Some(0),
+            from: 1,
+            to: 0,
+            attacker_losses: 0,
+            defender_losses: 1,
+        },
+        Event::Capture {
+            player: 1,
+            from: 1,
+            territory: 0,
+        },
+        Event::Defeat { player: 0, by: 1 },
+    ];
+
+    let view = GameView::new(&board, &players, &seats, 0, FogOfWar::Disabled, 1, &history);
+    assert_eq!(view.history, &history[..]);
+
+    // The claim of the other player and its reinforcements remain hidden
+    let view = GameView::new(&board, &players, &seats, 0, FogOfWar::Hidden, 1, &history);
+    let witnessed: Vec<Event> = [0, 2, 4, 5, 6]
+        .iter()
+        .map(|event| history[*event].clone())
+        .collect();
+    assert_eq!(view.history, witnessed);
+
+    // The owners remain visible
+    let view = GameView::new(&board, &players, &seats, 0, FogOfWar::Owners, 1, &history);
+    let witnessed: Vec<Event> = [0, 1, 2, 4, 5, 6]
+        .iter()
+        .map(|event| history[*event].clone())
+        .collect();
+    assert_eq!(view.history, witnessed);
+}